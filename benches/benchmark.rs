@@ -1,4 +1,4 @@
-//! Benchmark to compare different conversion methods.
+//! Benchmark to compare different conversion methods, bindings and clocks.
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 #![expect(
@@ -25,12 +25,32 @@ use rand::rngs::{OsRng, StdRng};
 use rand::{Rng, SeedableRng};
 use tests_web as _;
 use wasm_bindgen::closure::Closure;
+use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlTableElement, HtmlTableRowElement};
+#[cfg(feature = "std")]
+use web_time::web::CoarseClock;
+#[cfg(feature = "std")]
+use web_time::Clock;
+use web_time::Instant;
 
 /// Number of runs for the benchmark.
 const RUNS: usize = 100_000_000;
 
+#[wasm_bindgen]
+extern "C" {
+	/// Type for the [`Performance` object](https://developer.mozilla.org/en-US/docs/Web/API/Performance).
+	type Performance;
+
+	/// Holds the [`Performance`](https://developer.mozilla.org/en-US/docs/Web/API/Performance) object.
+	#[wasm_bindgen(thread_local_v2, js_namespace = globalThis, js_name = performance)]
+	static PERFORMANCE: Option<Performance>;
+
+	/// Binding to [`Performance.now()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now).
+	#[wasm_bindgen(method)]
+	fn now(this: &Performance) -> f64;
+}
+
 /// Main function.
 #[cfg_attr(not(feature = "std"), wasm_bindgen::prelude::wasm_bindgen(main))]
 pub fn main() {
@@ -67,15 +87,28 @@ pub fn main() {
 			}
 
 			let time = performance.now() - start;
-			let time = time / const { (RUNS / 1_000_000) as f64 };
+			insert_row(&table, &name, time);
+		});
 
-			let row: HtmlTableRowElement = table.insert_row().unwrap().unchecked_into();
-			let cell = row.insert_cell().unwrap();
-			cell.set_text_content(Some(&name));
-			cell.style().set_property("padding-right", "2em").unwrap();
-			row.insert_cell()
-				.unwrap()
-				.set_text_content(Some(&format!("{time:.2}ns")));
+		window
+			.set_timeout_with_callback(closure.unchecked_ref())
+			.unwrap();
+	};
+
+	let benchmark_call = |name: &str, run: fn()| {
+		let performance = performance.clone();
+		let table = table.clone();
+		let name = name.to_owned();
+
+		let closure = Closure::once_into_js(move || {
+			let start = performance.now();
+
+			for _ in 0..RUNS {
+				run();
+			}
+
+			let time = performance.now() - start;
+			insert_row(&table, &name, time);
 		});
 
 		window
@@ -118,6 +151,33 @@ pub fn main() {
 		// by rounding.
 		Duration::new(secs, nanos)
 	});
+	benchmark_call("`Performance.now()` with thread-local object", || {
+		hint::black_box(PERFORMANCE.with(|performance| performance.as_ref().unwrap().now()));
+	});
+	#[cfg(not(web_time_inline_js))]
+	let instant_now = "`Instant::now()`";
+	#[cfg(web_time_inline_js)]
+	let instant_now = "`Instant::now()` with `web_time_inline_js`";
+	benchmark_call(instant_now, || {
+		hint::black_box(Instant::now());
+	});
+	#[cfg(feature = "std")]
+	benchmark_call("`CoarseClock::now()`", || {
+		hint::black_box(CoarseClock.now());
+	});
+}
+
+/// Inserts the result of a benchmark into `table`.
+fn insert_row(table: &HtmlTableElement, name: &str, time: f64) {
+	let time = time / const { (RUNS / 1_000_000) as f64 };
+
+	let row: HtmlTableRowElement = table.insert_row().unwrap().unchecked_into();
+	let cell = row.insert_cell().unwrap();
+	cell.set_text_content(Some(name));
+	cell.style().set_property("padding-right", "2em").unwrap();
+	row.insert_cell()
+		.unwrap()
+		.set_text_content(Some(&format!("{time:.2}ns")));
 }
 
 /// [`f64`] `no_std` compatibility wrapper.
@@ -0,0 +1,401 @@
+//! Exponential backoff with jitter.
+
+use core::iter::FusedIterator;
+use core::time::Duration;
+
+use crate::clock::{Clock, DefaultClock};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+use crate::time::js;
+use crate::Instant;
+
+/// Source of random numbers for the jitter of [`Backoff`].
+///
+/// This trait is implemented for all closures returning a [`u64`], which
+/// allows to e.g. plug in the `rand` crate or a fixed sequence in tests.
+pub trait RandomSource {
+	/// Returns the next random [`u64`].
+	fn next_u64(&mut self) -> u64;
+}
+
+impl<F> RandomSource for F
+where
+	F: FnMut() -> u64,
+{
+	fn next_u64(&mut self) -> u64 {
+		self()
+	}
+}
+
+/// [`RandomSource`] implementing [SplitMix64], which is fast but not
+/// cryptographically secure.
+///
+/// It is seeded with [`Math.random()`] on Web and with
+/// [`RandomState`](std::collections::hash_map::RandomState) when re-exporting
+/// [`std::time`]. Targets without a native time source have no source of
+/// randomness, so it can only be created with
+/// [`from_seed()`](Self::from_seed) there.
+///
+/// [SplitMix64]: https://prng.di.unimi.it/splitmix64.c
+/// [`Math.random()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random
+#[derive(Clone, Debug)]
+// Copies would silently repeat the same sequence.
+#[allow(missing_copy_implementations)]
+pub struct DefaultRandom(u64);
+
+impl DefaultRandom {
+	/// Creates a new [`DefaultRandom`] with a random seed.
+	#[must_use]
+	#[cfg(not(all(not(target_arch = "wasm32"), target_os = "none")))]
+	pub fn new() -> Self {
+		Self(seed())
+	}
+
+	/// Creates a new [`DefaultRandom`] with the given `seed`.
+	#[must_use]
+	pub const fn from_seed(seed: u64) -> Self {
+		Self(seed)
+	}
+}
+
+#[cfg(not(all(not(target_arch = "wasm32"), target_os = "none")))]
+impl Default for DefaultRandom {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl RandomSource for DefaultRandom {
+	fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut value = self.0;
+		value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		value ^ (value >> 31)
+	}
+}
+
+/// Returns a random seed for [`DefaultRandom`].
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[allow(
+	clippy::as_conversions,
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss
+)]
+fn seed() -> u64 {
+	// `Math.random()` has at most 53 bits of precision.
+	(js::math_random() * 9_007_199_254_740_992.) as u64
+}
+
+/// Returns a random seed for [`DefaultRandom`].
+#[cfg(not(any(
+	all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	),
+	all(not(target_arch = "wasm32"), target_os = "none")
+)))]
+fn seed() -> u64 {
+	use std::collections::hash_map::RandomState;
+	use std::hash::{BuildHasher, Hasher};
+
+	RandomState::new().build_hasher().finish()
+}
+
+/// Iterator over the delays between retries, growing exponentially.
+///
+/// The delays are either doubling with [`Backoff::exponential()`] or randomly
+/// chosen with [`Backoff::decorrelated_jitter()`], which spreads out retries
+/// of many clients. Iteration ends after the configured number of retries or
+/// if a retry after the delay would exceed the maximum elapsed time or the
+/// deadline, after which it keeps returning [`None`] until
+/// [`reset()`](Self::reset).
+///
+/// The elapsed time is measured from when the [`Backoff`] was created or
+/// [`reset()`](Self::reset) with its [`Clock`].
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::{Backoff, Duration};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let backoff = Backoff::exponential(Duration::from_millis(100))
+/// 	.with_max_delay(Duration::from_secs(1))
+/// 	.with_max_retries(6);
+///
+/// let delays: Vec<_> = backoff.map(|delay| delay.as_millis()).collect();
+/// assert_eq!(delays, [100, 200, 400, 800, 1000, 1000]);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Backoff<R = DefaultRandom, C = DefaultClock> {
+	/// Time source.
+	clock: C,
+	/// Source of the jitter.
+	random: R,
+	/// How delays grow.
+	strategy: Strategy,
+	/// The first delay.
+	initial: Duration,
+	/// The upper limit of a single delay.
+	max_delay: Duration,
+	/// The next delay with [`Strategy::Exponential`] or the last delay with
+	/// [`Strategy::DecorrelatedJitter`].
+	current: Duration,
+	/// Maximum number of retries.
+	max_retries: Option<u32>,
+	/// Number of returned delays.
+	retries: u32,
+	/// Maximum time elapsed since `start` for a retry.
+	max_elapsed: Option<Duration>,
+	/// Latest [`Instant`] for a retry.
+	deadline: Option<Instant>,
+	/// When this [`Backoff`] was created or reset.
+	start: Instant,
+	/// If iteration ended, until [`reset()`](Self::reset).
+	exhausted: bool,
+}
+
+/// How delays of a [`Backoff`] grow.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Strategy {
+	/// See [`Backoff::exponential()`].
+	Exponential,
+	/// See [`Backoff::decorrelated_jitter()`].
+	DecorrelatedJitter,
+}
+
+impl Backoff {
+	/// Creates a [`Backoff`] starting with `initial` and doubling every
+	/// delay.
+	#[must_use]
+	pub fn exponential(initial: Duration) -> Self {
+		// Exponential delays don't use the random source.
+		Self::new(Strategy::Exponential, initial, DefaultRandom::from_seed(0))
+	}
+
+	/// Creates a [`Backoff`] with [decorrelated jitter], randomly choosing
+	/// every delay between `initial` and three times the previous delay.
+	///
+	/// [decorrelated jitter]: https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/
+	#[must_use]
+	#[cfg(not(all(not(target_arch = "wasm32"), target_os = "none")))]
+	pub fn decorrelated_jitter(initial: Duration) -> Self {
+		Self::new(Strategy::DecorrelatedJitter, initial, DefaultRandom::new())
+	}
+
+	/// Creates a [`Backoff`] with [decorrelated jitter], randomly choosing
+	/// every delay between `initial` and three times the previous delay.
+	///
+	/// There is no source of randomness on this target, so `random` has to be
+	/// supplied, e.g. [`DefaultRandom::from_seed()`] with a seed from a
+	/// hardware RNG.
+	///
+	/// [decorrelated jitter]: https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/
+	#[must_use]
+	#[cfg(all(not(target_arch = "wasm32"), target_os = "none"))]
+	pub fn decorrelated_jitter<R: RandomSource>(initial: Duration, random: R) -> Backoff<R> {
+		Backoff::new(Strategy::DecorrelatedJitter, initial, random)
+	}
+}
+
+impl<R> Backoff<R> {
+	/// Creates a [`Backoff`] with the given [`Strategy`] and [`RandomSource`].
+	fn new(strategy: Strategy, initial: Duration, random: R) -> Self {
+		Self {
+			clock: DefaultClock,
+			random,
+			strategy,
+			initial,
+			max_delay: Duration::MAX,
+			current: initial,
+			max_retries: None,
+			retries: 0,
+			max_elapsed: None,
+			deadline: None,
+			start: Instant::now(),
+			exhausted: false,
+		}
+	}
+}
+
+impl<R, C> Backoff<R, C> {
+	/// Uses the given [`RandomSource`] for the jitter.
+	pub fn with_random<R2: RandomSource>(self, random: R2) -> Backoff<R2, C> {
+		Backoff {
+			clock: self.clock,
+			random,
+			strategy: self.strategy,
+			initial: self.initial,
+			max_delay: self.max_delay,
+			current: self.current,
+			max_retries: self.max_retries,
+			retries: self.retries,
+			max_elapsed: self.max_elapsed,
+			deadline: self.deadline,
+			start: self.start,
+			exhausted: self.exhausted,
+		}
+	}
+
+	/// Uses the given [`Clock`] to measure the elapsed time, which restarts
+	/// now.
+	pub fn with_clock<C2: Clock>(self, clock: C2) -> Backoff<R, C2> {
+		let start = clock.now();
+
+		Backoff {
+			clock,
+			random: self.random,
+			strategy: self.strategy,
+			initial: self.initial,
+			max_delay: self.max_delay,
+			current: self.current,
+			max_retries: self.max_retries,
+			retries: self.retries,
+			max_elapsed: self.max_elapsed,
+			deadline: self.deadline,
+			start,
+			exhausted: self.exhausted,
+		}
+	}
+
+	/// Limits every delay to `max_delay`.
+	#[must_use]
+	pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+		self.max_delay = max_delay;
+		self
+	}
+
+	/// Ends iteration after `retries` delays.
+	#[must_use]
+	pub const fn with_max_retries(mut self, retries: u32) -> Self {
+		self.max_retries = Some(retries);
+		self
+	}
+
+	/// Ends iteration if the retry after the next delay would happen more than
+	/// `max_elapsed` after this [`Backoff`] was created or
+	/// [`reset()`](Self::reset).
+	#[must_use]
+	pub const fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+		self.max_elapsed = Some(max_elapsed);
+		self
+	}
+
+	/// Ends iteration if the retry after the next delay would happen after
+	/// `deadline`.
+	#[must_use]
+	pub const fn with_deadline(mut self, deadline: Instant) -> Self {
+		self.deadline = Some(deadline);
+		self
+	}
+
+	/// Returns the [`Clock`] used by this [`Backoff`].
+	pub const fn clock(&self) -> &C {
+		&self.clock
+	}
+
+	/// Returns the number of delays returned so far.
+	#[must_use]
+	pub const fn retries(&self) -> u32 {
+		self.retries
+	}
+
+	/// Returns the latest [`Instant`] for a retry, considering the maximum
+	/// elapsed time and the deadline.
+	#[must_use]
+	pub fn deadline(&self) -> Option<Instant> {
+		let elapsed = self
+			.max_elapsed
+			.and_then(|max_elapsed| self.start.checked_add(max_elapsed));
+
+		match (elapsed, self.deadline) {
+			(Some(elapsed), Some(deadline)) => Some(elapsed.min(deadline)),
+			(elapsed, deadline) => elapsed.or(deadline),
+		}
+	}
+}
+
+impl<R, C: Clock> Backoff<R, C> {
+	/// Starts over with the initial delay, no retries and the elapsed time
+	/// restarting now.
+	pub fn reset(&mut self) {
+		self.current = self.initial;
+		self.retries = 0;
+		self.start = self.clock.now();
+		self.exhausted = false;
+	}
+}
+
+impl<R: RandomSource, C: Clock> Iterator for Backoff<R, C> {
+	type Item = Duration;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.exhausted {
+			return None;
+		}
+
+		if let Some(max_retries) = self.max_retries {
+			if self.retries >= max_retries {
+				self.exhausted = true;
+				return None;
+			}
+		}
+
+		let (delay, current) = match self.strategy {
+			Strategy::Exponential => (
+				self.current.min(self.max_delay),
+				self.current.checked_mul(2).unwrap_or(Duration::MAX),
+			),
+			Strategy::DecorrelatedJitter => {
+				let upper = self.current.checked_mul(3).unwrap_or(Duration::MAX);
+				let delay =
+					random_between(&mut self.random, self.initial, upper).min(self.max_delay);
+				(delay, delay)
+			}
+		};
+
+		if let Some(deadline) = self.deadline() {
+			match self.clock.now().checked_add(delay) {
+				Some(retry) if retry <= deadline => (),
+				_ => {
+					self.exhausted = true;
+					return None;
+				}
+			}
+		}
+
+		self.current = current;
+		self.retries = self.retries.saturating_add(1);
+
+		Some(delay)
+	}
+}
+
+impl<R: RandomSource, C: Clock> FusedIterator for Backoff<R, C> {}
+
+/// Returns a random [`Duration`] between `low` and `high`, with a resolution
+/// of nanoseconds up to [`u64::MAX`].
+fn random_between<R: RandomSource>(random: &mut R, low: Duration, high: Duration) -> Duration {
+	let low = u64::try_from(low.as_nanos()).unwrap_or(u64::MAX);
+	let high = u64::try_from(high.as_nanos()).unwrap_or(u64::MAX);
+	let range = high.saturating_sub(low);
+
+	let offset = match range.checked_add(1) {
+		Some(range) => random.next_u64() % range,
+		None => random.next_u64(),
+	};
+
+	Duration::from_nanos(low.saturating_add(offset))
+}
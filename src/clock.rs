@@ -0,0 +1,124 @@
+//! Time source abstraction used by the utilities in this crate.
+
+use crate::Instant;
+
+/// Source of [`Instant`]s.
+///
+/// Utilities like [`Stopwatch`](crate::Stopwatch) are generic over their time
+/// source, which allows to replace [`Instant::now()`] with a manually
+/// controlled clock in tests. This trait is implemented for all closures
+/// returning an [`Instant`].
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use core::cell::Cell;
+///
+/// use web_time::{Clock, Duration, Instant};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let start = Instant::now();
+/// let offset = Cell::new(Duration::ZERO);
+/// let clock = || start + offset.get();
+///
+/// offset.set(Duration::from_secs(1));
+/// assert_eq!(clock.now() - start, Duration::from_secs(1));
+/// # }
+/// ```
+pub trait Clock {
+	/// Returns the current [`Instant`] of this clock.
+	///
+	/// Must be monotonic, otherwise measurements will saturate at zero.
+	fn now(&self) -> Instant;
+}
+
+impl<F> Clock for F
+where
+	F: Fn() -> Instant,
+{
+	fn now(&self) -> Instant {
+		self()
+	}
+}
+
+/// [`Clock`] using [`Instant::now()`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DefaultClock;
+
+impl Clock for DefaultClock {
+	fn now(&self) -> Instant {
+		Instant::now()
+	}
+}
+
+/// Registers the clock source used by [`Instant::now()`] and
+/// [`SystemTime::now()`] on targets without a native time source, e.g.
+/// microcontrollers or kernels.
+///
+/// The first function is used by [`Instant::now()`] and has to return a
+/// monotonic [`Duration`] since an arbitrary, but fixed, point in time. The
+/// optional second function is used by [`SystemTime::now()`] and has to return
+/// the [`Duration`] since [`UNIX_EPOCH`]. If it is not supplied,
+/// [`SystemTime::now()`] will panic.
+///
+/// This macro must be called exactly once in the final binary. Failing to
+/// call it will result in a linker error. On all other targets the registered
+/// functions are not used, which allows to call it unconditionally.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::Duration;
+///
+/// fn instant_now() -> Duration {
+/// 	// E.g. read a hardware timer.
+/// 	# Duration::ZERO
+/// }
+///
+/// fn system_time_now() -> Duration {
+/// 	// E.g. read a real-time clock.
+/// 	# Duration::ZERO
+/// }
+///
+/// web_time::register_clock!(instant_now, system_time_now);
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {}
+/// ```
+///
+/// [`Duration`]: crate::Duration
+/// [`Instant::now()`]: crate::Instant::now
+/// [`SystemTime::now()`]: crate::SystemTime::now
+/// [`UNIX_EPOCH`]: crate::UNIX_EPOCH
+#[macro_export]
+macro_rules! register_clock {
+	($instant:path, $system_time:expr $(,)?) => {
+		const _: () = {
+			#[no_mangle]
+			extern "Rust" fn __web_time_instant_now() -> $crate::Duration {
+				let instant: fn() -> $crate::Duration = $instant;
+				instant()
+			}
+
+			#[no_mangle]
+			extern "Rust" fn __web_time_system_time_now() -> $crate::Duration {
+				let system_time: fn() -> $crate::Duration = $system_time;
+				system_time()
+			}
+		};
+	};
+	($instant:path $(,)?) => {
+		$crate::register_clock!($instant, {
+			|| -> $crate::Duration { panic!("no system time clock registered") }
+		});
+	};
+}
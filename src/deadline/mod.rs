@@ -0,0 +1,127 @@
+//! Deadline propagated through layers of timeouts.
+
+#[cfg(feature = "serde")]
+mod serde;
+
+use core::time::Duration;
+
+use crate::Instant;
+
+/// Point in time by which an operation has to complete, which may be
+/// [`NEVER`](Self::NEVER).
+///
+/// Passing a [`Deadline`] instead of a [`Duration`] through layers of calls
+/// keeps the overall time budget intact, while [`remaining()`](Self::remaining)
+/// converts it back into a timeout where needed.
+///
+/// With the `serde` crate feature, it implements [`Deserialize`] and
+/// [`Serialize`] as the remaining [`Duration`], or [`None`] for
+/// [`NEVER`](Self::NEVER), because [`Instant`]s can't be compared across e.g.
+/// Web workers.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::{Deadline, Duration};
+///
+/// fn request(deadline: Deadline) {
+/// 	// Never wait longer than a second per attempt.
+/// 	let attempt = deadline.min(Deadline::from_timeout(Duration::from_secs(1)));
+/// 	assert!(attempt.remaining() <= Duration::from_secs(1));
+/// }
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// request(Deadline::from_timeout(Duration::from_secs(10)));
+/// request(Deadline::NEVER);
+/// # }
+/// ```
+#[cfg_attr(
+	feature = "serde",
+	doc = "",
+	doc = "[`Deserialize`]: ::serde::Deserialize",
+	doc = "[`Serialize`]: ::serde::Serialize"
+)]
+#[cfg_attr(
+	not(feature = "serde"),
+	doc = "",
+	doc = "[`Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html",
+	doc = "[`Serialize`]: https://docs.rs/serde/1/serde/trait.Serialize.html"
+)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Deadline(Option<Instant>);
+
+impl Deadline {
+	/// [`Deadline`] that never expires.
+	pub const NEVER: Self = Self(None);
+
+	/// Creates a [`Deadline`] expiring at `instant`.
+	#[must_use]
+	pub const fn at(instant: Instant) -> Self {
+		Self(Some(instant))
+	}
+
+	/// Creates a [`Deadline`] expiring after `timeout`.
+	///
+	/// Returns [`NEVER`](Self::NEVER) if the deadline can't be represented by
+	/// [`Instant`].
+	#[must_use]
+	pub fn from_timeout(timeout: Duration) -> Self {
+		Self(Instant::now().checked_add(timeout))
+	}
+
+	/// Returns the [`Instant`] at which this [`Deadline`] expires, or [`None`]
+	/// if it's [`NEVER`](Self::NEVER).
+	#[must_use]
+	pub const fn instant(self) -> Option<Instant> {
+		self.0
+	}
+
+	/// Returns the time until this [`Deadline`] expires, which is zero if it
+	/// already has.
+	///
+	/// Returns [`Duration::MAX`] for [`NEVER`](Self::NEVER).
+	#[must_use]
+	pub fn remaining(self) -> Duration {
+		self.0.map_or(Duration::MAX, |instant| {
+			instant.saturating_duration_since(Instant::now())
+		})
+	}
+
+	/// Returns [`true`] if this [`Deadline`] has expired.
+	#[must_use]
+	pub fn is_expired(self) -> bool {
+		self.0.map_or(false, |instant| instant <= Instant::now())
+	}
+
+	/// Returns the earlier of both [`Deadline`]s.
+	#[must_use]
+	pub fn min(self, other: Self) -> Self {
+		match (self.0, other.0) {
+			(Some(this), Some(other)) => Self(Some(this.min(other))),
+			(this, other) => Self(this.or(other)),
+		}
+	}
+
+	/// Returns this [`Deadline`] postponed by `duration`, or [`None`] if it
+	/// can't be represented by [`Instant`].
+	///
+	/// [`NEVER`](Self::NEVER) stays [`NEVER`](Self::NEVER).
+	#[must_use]
+	pub fn checked_add(self, duration: Duration) -> Option<Self> {
+		match self.0 {
+			Some(instant) => instant.checked_add(duration).map(Self::at),
+			None => Some(Self::NEVER),
+		}
+	}
+}
+
+impl From<Instant> for Deadline {
+	fn from(instant: Instant) -> Self {
+		Self::at(instant)
+	}
+}
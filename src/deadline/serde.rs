@@ -0,0 +1,30 @@
+//! Serde serialization and de-serialization for [`Deadline`].
+//!
+//! The remaining time is serialized as an [`Option<Duration>`], with [`None`]
+//! for [`Deadline::NEVER`].
+
+use core::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Deadline;
+
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "serde")))]
+impl Serialize for Deadline {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		self.0.map(|_| self.remaining()).serialize(serializer)
+	}
+}
+
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Deadline {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Ok(Option::<Duration>::deserialize(deserializer)?.map_or(Self::NEVER, Self::from_timeout))
+	}
+}
@@ -0,0 +1,409 @@
+//! Histogram recording latencies in log-linear buckets.
+
+#[cfg(feature = "serde")]
+mod serde;
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+use crate::clock::{Clock, DefaultClock};
+use crate::Instant;
+
+/// Number of bits used for the linear sub-buckets.
+const SUB_BITS: u32 = 5;
+/// Number of linear sub-buckets per power of two.
+const SUB_BUCKETS: usize = 1 << SUB_BITS;
+/// Total number of buckets to cover all [`u64`] values: one group of
+/// `SUB_BUCKETS` for values below `SUB_BUCKETS` and one for every power of two
+/// above.
+const BUCKETS: usize = 60 * SUB_BUCKETS;
+
+/// Histogram recording [`Duration`]s to compute percentiles, e.g. of frame
+/// times or request latencies.
+///
+/// Values are recorded in nanoseconds into log-linear buckets, similar to
+/// [HdrHistogram], with a relative error of at most ~3%. Recording only
+/// requires a shared reference, so a [`Histogram`] can be shared between
+/// threads, e.g. in an [`Arc`](alloc::sync::Arc). Statistics are computed on
+/// a [`HistogramSnapshot`], which can be merged with others.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::{Duration, Histogram, Instant};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let histogram = Histogram::new();
+///
+/// let start = Instant::now();
+/// // Do some work.
+/// histogram.record_since(start);
+///
+/// let result = histogram.time(|| {
+/// 	// Do some more work.
+/// 	42
+/// });
+/// # let _ = result;
+///
+/// histogram.record(Duration::from_millis(16));
+///
+/// let snapshot = histogram.snapshot();
+/// assert_eq!(snapshot.count(), 3);
+/// assert!(snapshot.p99() <= snapshot.max());
+/// # }
+/// ```
+///
+/// [HdrHistogram]: https://hdrhistogram.github.io/HdrHistogram/
+pub struct Histogram<C = DefaultClock> {
+	/// Time source.
+	clock: C,
+	/// Number of recorded values per bucket.
+	counts: Box<[AtomicU64]>,
+	/// Smallest recorded value in nanoseconds, [`u64::MAX`] if empty.
+	min: AtomicU64,
+	/// Largest recorded value in nanoseconds.
+	max: AtomicU64,
+}
+
+impl Histogram {
+	/// Creates an empty [`Histogram`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::with_clock(DefaultClock)
+	}
+}
+
+impl Default for Histogram {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<C> Histogram<C> {
+	/// Creates an empty [`Histogram`] using the given [`Clock`] for
+	/// [`record_since()`](Self::record_since) and [`time()`](Self::time).
+	pub fn with_clock(clock: C) -> Self {
+		Self {
+			clock,
+			counts: iter::repeat_with(|| AtomicU64::new(0))
+				.take(BUCKETS)
+				.collect(),
+			min: AtomicU64::new(u64::MAX),
+			max: AtomicU64::new(0),
+		}
+	}
+
+	/// Returns the [`Clock`] used by this [`Histogram`].
+	pub const fn clock(&self) -> &C {
+		&self.clock
+	}
+
+	/// Records a [`Duration`]. Durations exceeding [`u64::MAX`] nanoseconds
+	/// are saturated.
+	pub fn record(&self, duration: Duration) {
+		let value = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+
+		// Update the extremes first and publish them with the count, so a
+		// snapshot observing the count also observes them.
+		self.min.fetch_min(value, Ordering::Relaxed);
+		self.max.fetch_max(value, Ordering::Relaxed);
+		bucket(&self.counts, index(value)).fetch_add(1, Ordering::Release);
+	}
+
+	/// Returns a [`HistogramSnapshot`] of all values recorded so far.
+	///
+	/// Values recorded concurrently may or may not be included.
+	#[must_use]
+	pub fn snapshot(&self) -> HistogramSnapshot {
+		let counts: Vec<u64> = self
+			.counts
+			.iter()
+			.map(|count| count.load(Ordering::Acquire))
+			.collect();
+		let count = counts
+			.iter()
+			.fold(0_u64, |sum, count| sum.saturating_add(*count));
+		let mut min = self.min.load(Ordering::Relaxed);
+		let mut max = self.max.load(Ordering::Relaxed);
+
+		// A concurrent `reset()` can discard the extremes of counted values.
+		if count != 0 && min > max {
+			let mut recorded = counts
+				.iter()
+				.enumerate()
+				.filter(|(_, count)| **count != 0)
+				.map(|(index, _)| index);
+			let first = recorded.next().unwrap_or(0);
+			let last = recorded.next_back().unwrap_or(first);
+			min = lowest_equivalent(first);
+			max = highest_equivalent(last);
+		}
+
+		HistogramSnapshot {
+			counts,
+			count,
+			min,
+			max,
+		}
+	}
+
+	/// Discards all recorded values.
+	///
+	/// Values recorded concurrently may or may not be discarded.
+	pub fn reset(&self) {
+		for count in self.counts.iter() {
+			count.store(0, Ordering::Relaxed);
+		}
+
+		self.min.store(u64::MAX, Ordering::Relaxed);
+		self.max.store(0, Ordering::Relaxed);
+	}
+}
+
+impl<C: Clock> Histogram<C> {
+	/// Records the time elapsed since `start` and returns it.
+	pub fn record_since(&self, start: Instant) -> Duration {
+		let elapsed = self.clock.now().saturating_duration_since(start);
+		self.record(elapsed);
+		elapsed
+	}
+
+	/// Calls `fun`, records how long it took and returns its output.
+	pub fn time<R, F: FnOnce() -> R>(&self, fun: F) -> R {
+		let start = self.clock.now();
+		let output = fun();
+		self.record_since(start);
+		output
+	}
+}
+
+impl<C: Debug> Debug for Histogram<C> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Histogram")
+			.field("clock", &self.clock)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Snapshot of the values recorded by a [`Histogram`], which computes the
+/// statistics.
+///
+/// All returned [`Duration`]s are accurate to ~3% and are [`None`] if no
+/// values were recorded. With the `serde` crate feature, it implements
+/// [`Deserialize`] and [`Serialize`] to e.g. merge snapshots from different
+/// clients.
+#[cfg_attr(
+	feature = "serde",
+	doc = "",
+	doc = "[`Deserialize`]: ::serde::Deserialize",
+	doc = "[`Serialize`]: ::serde::Serialize"
+)]
+#[cfg_attr(
+	not(feature = "serde"),
+	doc = "",
+	doc = "[`Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html",
+	doc = "[`Serialize`]: https://docs.rs/serde/1/serde/trait.Serialize.html"
+)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct HistogramSnapshot {
+	/// Number of recorded values per bucket.
+	counts: Vec<u64>,
+	/// Total number of recorded values.
+	count: u64,
+	/// Smallest recorded value in nanoseconds, [`u64::MAX`] if empty.
+	min: u64,
+	/// Largest recorded value in nanoseconds.
+	max: u64,
+}
+
+impl HistogramSnapshot {
+	/// Creates an empty [`HistogramSnapshot`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			counts: vec_of_zeros(),
+			count: 0,
+			min: u64::MAX,
+			max: 0,
+		}
+	}
+
+	/// Returns the number of recorded values.
+	#[must_use]
+	pub const fn count(&self) -> u64 {
+		self.count
+	}
+
+	/// Returns [`true`] if no values were recorded.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.count == 0
+	}
+
+	/// Returns the smallest recorded value.
+	#[must_use]
+	pub const fn min(&self) -> Option<Duration> {
+		if self.is_empty() {
+			None
+		} else {
+			Some(Duration::from_nanos(self.min))
+		}
+	}
+
+	/// Returns the largest recorded value.
+	#[must_use]
+	pub const fn max(&self) -> Option<Duration> {
+		if self.is_empty() {
+			None
+		} else {
+			Some(Duration::from_nanos(self.max))
+		}
+	}
+
+	/// Returns the value below or at which the given `quantile` of all
+	/// recorded values fall, e.g. `0.99` for the 99th percentile.
+	///
+	/// # Panics
+	///
+	/// If `quantile` is not between `0` and `1`.
+	#[must_use]
+	pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+		assert!(
+			(0. ..=1.).contains(&quantile),
+			"`quantile` must be between `0` and `1`"
+		);
+
+		if self.is_empty() {
+			return None;
+		}
+
+		let rank = rank(self.count, quantile);
+		let mut seen = 0_u64;
+
+		for (index, count) in self.counts.iter().enumerate() {
+			seen = seen.saturating_add(*count);
+
+			if seen >= rank {
+				let mut value = highest_equivalent(index);
+
+				if self.min <= self.max {
+					value = value.clamp(self.min, self.max);
+				}
+
+				return Some(Duration::from_nanos(value));
+			}
+		}
+
+		self.max()
+	}
+
+	/// Returns the median.
+	#[must_use]
+	pub fn p50(&self) -> Option<Duration> {
+		self.quantile(0.5)
+	}
+
+	/// Returns the 90th percentile.
+	#[must_use]
+	pub fn p90(&self) -> Option<Duration> {
+		self.quantile(0.9)
+	}
+
+	/// Returns the 99th percentile.
+	#[must_use]
+	pub fn p99(&self) -> Option<Duration> {
+		self.quantile(0.99)
+	}
+
+	/// Adds all values recorded in `other` to this [`HistogramSnapshot`].
+	pub fn merge(&mut self, other: &Self) {
+		for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+			*count = count.saturating_add(*other);
+		}
+
+		self.count = self.count.saturating_add(other.count);
+		self.min = self.min.min(other.min);
+		self.max = self.max.max(other.max);
+	}
+}
+
+impl Default for HistogramSnapshot {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Debug for HistogramSnapshot {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("HistogramSnapshot")
+			.field("count", &self.count)
+			.field("min", &self.min())
+			.field("max", &self.max())
+			.finish_non_exhaustive()
+	}
+}
+
+/// Returns a [`Vec`] with a zero for every bucket.
+fn vec_of_zeros() -> Vec<u64> {
+	iter::repeat(0).take(BUCKETS).collect()
+}
+
+/// Returns the bucket at `index`.
+fn bucket(counts: &[AtomicU64], index: usize) -> &AtomicU64 {
+	counts.get(index).expect("found invalid bucket index")
+}
+
+/// Returns the index of the bucket `value` is recorded in.
+///
+/// Values smaller than `2 * SUB_BUCKETS` have their own bucket. Every power of
+/// two above is split into `SUB_BUCKETS` linear buckets.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+fn index(value: u64) -> usize {
+	let shift = (64 - value.leading_zeros()).saturating_sub(SUB_BITS + 1);
+	((u64::from(shift) << SUB_BITS) + (value >> shift)) as usize
+}
+
+/// Returns the smallest value recorded in the bucket at `index`.
+#[allow(clippy::as_conversions)]
+const fn lowest_equivalent(index: usize) -> u64 {
+	let index = index as u64;
+	let shift = (index >> SUB_BITS).saturating_sub(1);
+	(index - (shift << SUB_BITS)) << shift
+}
+
+/// Returns the largest value recorded in the bucket at `index`.
+#[allow(clippy::as_conversions)]
+const fn highest_equivalent(index: usize) -> u64 {
+	let shift = ((index as u64) >> SUB_BITS).saturating_sub(1);
+	lowest_equivalent(index) + ((1 << shift) - 1)
+}
+
+/// Returns the 1-based rank of `quantile` in `count` values.
+#[allow(
+	clippy::as_conversions,
+	clippy::cast_possible_truncation,
+	clippy::cast_precision_loss,
+	clippy::cast_sign_loss
+)]
+fn rank(count: u64, quantile: f64) -> u64 {
+	let exact = quantile * count as f64;
+	let mut rank = exact as u64;
+
+	// Round up without `f64::ceil()`, which is not available in `no_std`.
+	if (rank as f64) < exact {
+		rank += 1;
+	}
+
+	rank.clamp(1, count)
+}
@@ -0,0 +1,249 @@
+//! Serde serialization and de-serialization for [`HistogramSnapshot`].
+//!
+//! Only non-empty buckets are serialized, as a sequence of index and count
+//! pairs.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+use core::time::Duration;
+
+use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{vec_of_zeros, HistogramSnapshot, BUCKETS};
+
+/// Names of the serialized fields.
+const FIELDS: &[&str] = &["min", "max", "buckets"];
+
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "serde")))]
+impl Serialize for HistogramSnapshot {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut state = serializer.serialize_struct("HistogramSnapshot", FIELDS.len())?;
+		state.serialize_field("min", &self.min())?;
+		state.serialize_field("max", &self.max())?;
+		state.serialize_field("buckets", &Buckets(&self.counts))?;
+		state.end()
+	}
+}
+
+/// Serializes non-empty buckets.
+struct Buckets<'counts>(&'counts [u64]);
+
+impl Serialize for Buckets<'_> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let buckets = self.0.iter().enumerate().filter(|(_, count)| **count != 0);
+
+		let mut seq = serializer.serialize_seq(Some(buckets.clone().count()))?;
+
+		for (index, count) in buckets {
+			seq.serialize_element(&(index, count))?;
+		}
+
+		seq.end()
+	}
+}
+
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for HistogramSnapshot {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_struct("HistogramSnapshot", FIELDS, SnapshotVisitor)
+	}
+}
+
+/// Field of a serialized [`HistogramSnapshot`].
+enum Field {
+	/// Smallest recorded value.
+	Min,
+	/// Largest recorded value.
+	Max,
+	/// Non-empty buckets.
+	Buckets,
+}
+
+impl<'de> Deserialize<'de> for Field {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_identifier(FieldVisitor)
+	}
+}
+
+/// [`Visitor`] for [`Field`].
+struct FieldVisitor;
+
+impl Visitor<'_> for FieldVisitor {
+	type Value = Field;
+
+	fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str("`min`, `max` or `buckets`")
+	}
+
+	#[allow(clippy::renamed_function_params)]
+	fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+		match value {
+			"min" => Ok(Field::Min),
+			"max" => Ok(Field::Max),
+			"buckets" => Ok(Field::Buckets),
+			_ => Err(E::unknown_field(value, FIELDS)),
+		}
+	}
+}
+
+/// [`Visitor`] for [`HistogramSnapshot`].
+struct SnapshotVisitor;
+
+impl<'de> Visitor<'de> for SnapshotVisitor {
+	type Value = HistogramSnapshot;
+
+	fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str("struct HistogramSnapshot")
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let min = seq
+			.next_element()?
+			.ok_or_else(|| Error::invalid_length(0, &self))?;
+		let max = seq
+			.next_element()?
+			.ok_or_else(|| Error::invalid_length(1, &self))?;
+		let BucketsOwned(counts) = seq
+			.next_element()?
+			.ok_or_else(|| Error::invalid_length(2, &self))?;
+
+		snapshot(counts, min, max)
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		let mut min = None;
+		let mut max = None;
+		let mut counts = None;
+
+		while let Some(key) = map.next_key()? {
+			match key {
+				Field::Min => next_value(&mut map, &mut min, "min")?,
+				Field::Max => next_value(&mut map, &mut max, "max")?,
+				Field::Buckets => {
+					let mut buckets = None;
+					next_value(&mut map, &mut buckets, "buckets")?;
+					counts = buckets.map(|BucketsOwned(counts)| counts);
+				}
+			}
+		}
+
+		snapshot(
+			counts.ok_or_else(|| Error::missing_field("buckets"))?,
+			min.ok_or_else(|| Error::missing_field("min"))?,
+			max.ok_or_else(|| Error::missing_field("max"))?,
+		)
+	}
+}
+
+/// Deserializes the next value of `map` into `field`, which must not be set
+/// yet.
+fn next_value<'de, A, T>(
+	map: &mut A,
+	field: &mut Option<T>,
+	name: &'static str,
+) -> Result<(), A::Error>
+where
+	A: MapAccess<'de>,
+	T: Deserialize<'de>,
+{
+	if field.is_some() {
+		return Err(Error::duplicate_field(name));
+	}
+
+	*field = Some(map.next_value()?);
+	Ok(())
+}
+
+/// Builds a [`HistogramSnapshot`], checking that `min` and `max` are only
+/// present if any values were recorded.
+fn snapshot<E: Error>(
+	counts: Vec<u64>,
+	min: Option<Duration>,
+	max: Option<Duration>,
+) -> Result<HistogramSnapshot, E> {
+	let count = counts
+		.iter()
+		.fold(0_u64, |sum, count| sum.saturating_add(*count));
+
+	let (min, max) = match (count, min, max) {
+		(0, None, None) => (u64::MAX, 0),
+		(_, Some(min), Some(max)) if count != 0 && min <= max => (nanos(min)?, nanos(max)?),
+		_ => return Err(E::custom("`min` and `max` don't match `buckets`")),
+	};
+
+	Ok(HistogramSnapshot {
+		counts,
+		count,
+		min,
+		max,
+	})
+}
+
+/// Converts a [`Duration`] to nanoseconds.
+fn nanos<E: Error>(duration: Duration) -> Result<u64, E> {
+	u64::try_from(duration.as_nanos())
+		.map_err(|_| E::custom("`Duration` exceeds `u64` nanoseconds"))
+}
+
+/// De-serializes non-empty buckets.
+struct BucketsOwned(Vec<u64>);
+
+impl<'de> Deserialize<'de> for BucketsOwned {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_seq(BucketsVisitor)
+	}
+}
+
+/// [`Visitor`] for [`BucketsOwned`].
+struct BucketsVisitor;
+
+impl<'de> Visitor<'de> for BucketsVisitor {
+	type Value = BucketsOwned;
+
+	fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str("a sequence of bucket indices and counts")
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let mut counts = vec_of_zeros();
+
+		while let Some((index, count)) = seq.next_element::<(usize, u64)>()? {
+			let bucket = counts.get_mut(index).ok_or_else(|| {
+				Error::custom(format_args!(
+					"bucket index {index} out of range, expected less than {BUCKETS}"
+				))
+			})?;
+			*bucket = bucket.saturating_add(count);
+		}
+
+		Ok(BucketsOwned(counts))
+	}
+}
@@ -0,0 +1,378 @@
+//! Formatting and parsing of [HTTP dates] as used by e.g. the `Date`,
+//! `Expires`, `Last-Modified` and `Retry-After` headers.
+//!
+//! Dates are formatted as IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+//! Additionally the obsolete RFC 850, e.g. `Sunday, 06-Nov-94 08:49:37 GMT`,
+//! and asctime, e.g. `Sun Nov  6 08:49:37 1994`, formats are accepted when
+//! parsing. Two-digit years of RFC 850 dates are interpreted relative to the
+//! current year, see [`HttpDate::parse_with_year()`].
+//!
+//! # Example
+//!
+//! ```
+//! # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+//! #
+//! # #[cfg(target_arch = "wasm32")]
+//! # use tests_web as _;
+//! use web_time::httpdate::HttpDateExt;
+//! use web_time::{Duration, SystemTime};
+//!
+//! # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+//! # fn main() {
+//! let time = SystemTime::from_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+//! assert_eq!(
+//! 	time,
+//! 	SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777)
+//! );
+//!
+//! let date = time.to_http_date().unwrap();
+//! # #[cfg(feature = "std")]
+//! assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+//! # }
+//! ```
+//!
+//! [HTTP dates]: https://httpwg.org/specs/rfc9110.html#http.date
+
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use core::time::Duration;
+#[cfg(any(
+	all(target_arch = "wasm32", feature = "std"),
+	all(not(target_arch = "wasm32"), not(target_os = "none"))
+))]
+use std::error::Error;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "std"
+))]
+use std::time::SystemTime as StdSystemTime;
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "std"
+))]
+use crate::web::SystemTimeExt;
+use crate::{SystemTime, UtcDateTime, UNIX_EPOCH};
+
+/// Abbreviated day names, starting with Monday.
+const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Full day names, starting with Monday.
+const FULL_DAYS: [&str; 7] = [
+	"Monday",
+	"Tuesday",
+	"Wednesday",
+	"Thursday",
+	"Friday",
+	"Saturday",
+	"Sunday",
+];
+
+/// Abbreviated month names.
+const MONTHS: [&str; 12] = [
+	"Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Seconds since [`UNIX_EPOCH`] of `10000-01-01T00:00:00Z`, the first
+/// [`SystemTime`] not representable as an [`HttpDate`].
+const MAX: u64 = 253_402_300_800;
+
+/// A [`SystemTime`] with second precision between the years 1970 and 9999 that
+/// can be formatted and parsed as an HTTP date.
+///
+/// Its [`Display`] implementation formats it as IMF-fixdate, its [`FromStr`]
+/// implementation additionally accepts the obsolete RFC 850 and asctime
+/// formats.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct HttpDate(u64);
+
+impl HttpDate {
+	/// Parses an IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+	fn parse_imf_fixdate(date: &[u8]) -> Option<Self> {
+		#[rustfmt::skip]
+		let (weekday, day, month, year, time) = match *<&[u8; 29]>::try_from(date).ok()? {
+			[
+				w0, w1, w2, b',', b' ',
+				d0, d1, b' ',
+				m0, m1, m2, b' ',
+				y0, y1, y2, y3, b' ',
+				h0, h1, b':', n0, n1, b':', s0, s1,
+				b' ', b'G', b'M', b'T',
+			] => ([w0, w1, w2], [d0, d1], [m0, m1, m2], [y0, y1, y2, y3], [h0, h1, n0, n1, s0, s1]),
+			_ => return None,
+		};
+
+		Self::from_bytes(&DAYS, &weekday, day, month, digits(&year)?, time)
+	}
+
+	/// Parses an RFC 850 date, e.g. `Sunday, 06-Nov-94 08:49:37 GMT`, with
+	/// two-digit years relative to `current_year`.
+	fn parse_rfc850(date: &[u8], current_year: i64) -> Option<Self> {
+		let (weekday, date) = date.split_at(date.iter().position(|byte| *byte == b',')?);
+
+		#[rustfmt::skip]
+		let (day, month, year, time) = match *<&[u8; 24]>::try_from(date).ok()? {
+			[
+				b',', b' ',
+				d0, d1, b'-',
+				m0, m1, m2, b'-',
+				y0, y1, b' ',
+				h0, h1, b':', n0, n1, b':', s0, s1,
+				b' ', b'G', b'M', b'T',
+			] => ([d0, d1], [m0, m1, m2], [y0, y1], [h0, h1, n0, n1, s0, s1]),
+			_ => return None,
+		};
+
+		let year = i64::from(digits(&year)?);
+		let mut year = current_year - current_year.rem_euclid(100) + year;
+
+		if year > current_year + 50 {
+			year -= 100;
+		}
+
+		let year = u16::try_from(year).ok()?;
+
+		Self::from_bytes(&FULL_DAYS, weekday, day, month, year, time)
+	}
+
+	/// Parses an asctime date, e.g. `Sun Nov  6 08:49:37 1994`.
+	fn parse_asctime(date: &[u8]) -> Option<Self> {
+		#[rustfmt::skip]
+		let (weekday, day, month, year, time) = match *<&[u8; 24]>::try_from(date).ok()? {
+			[
+				w0, w1, w2, b' ',
+				m0, m1, m2, b' ',
+				d0, d1, b' ',
+				h0, h1, b':', n0, n1, b':', s0, s1, b' ',
+				y0, y1, y2, y3,
+			] => ([w0, w1, w2], [d0, d1], [m0, m1, m2], [y0, y1, y2, y3], [h0, h1, n0, n1, s0, s1]),
+			_ => return None,
+		};
+
+		// Single-digit days are padded with a space.
+		let day = match day {
+			[b' ', d1] => [b'0', d1],
+			day => day,
+		};
+
+		Self::from_bytes(&DAYS, &weekday, day, month, digits(&year)?, time)
+	}
+
+	/// Parses an HTTP date in any of the supported formats, interpreting
+	/// two-digit years of RFC 850 dates relative to `current_year`.
+	///
+	/// Two-digit years are interpreted as in the current century, unless that
+	/// appears to be more than 50 years in the future, in which case the most
+	/// recent year in the past with the same last two digits is used, as
+	/// required by [RFC 9110]. [`FromStr`] uses the year of
+	/// [`SystemTime::now()`], except on targets without a native time source,
+	/// where it fails to parse RFC 850 dates, because a system time clock might
+	/// not be registered.
+	///
+	/// # Errors
+	///
+	/// If `date` isn't a valid HTTP date.
+	///
+	/// [RFC 9110]: https://httpwg.org/specs/rfc9110.html#rfc.section.5.6.7
+	pub fn parse_with_year(date: &str, current_year: i64) -> Result<Self, HttpDateError> {
+		Self::parse(date, Some(current_year))
+	}
+
+	/// Parses an HTTP date, failing for RFC 850 dates without `current_year`.
+	fn parse(date: &str, current_year: Option<i64>) -> Result<Self, HttpDateError> {
+		let date = date.as_bytes();
+
+		Self::parse_imf_fixdate(date)
+			.or_else(|| Self::parse_rfc850(date, current_year?))
+			.or_else(|| Self::parse_asctime(date))
+			.ok_or(HttpDateError)
+	}
+
+	/// Validates the unparsed components and converts them to an
+	/// [`HttpDate`].
+	fn from_bytes(
+		weekdays: &[&str],
+		weekday: &[u8],
+		day: [u8; 2],
+		month: [u8; 3],
+		year: u16,
+		[h0, h1, n0, n1, s0, s1]: [u8; 6],
+	) -> Option<Self> {
+		let weekday = index_of(weekdays, weekday)?;
+		let second = u8::try_from(digits(&[s0, s1])?).ok()?;
+		let date_time = UtcDateTime::new(
+			year.into(),
+			index_of(&MONTHS, &month)? + 1,
+			u8::try_from(digits(&day)?).ok()?,
+			u8::try_from(digits(&[h0, h1])?).ok()?,
+			u8::try_from(digits(&[n0, n1])?).ok()?,
+			second.min(59),
+			0,
+		)?;
+
+		if !(1970..=9999).contains(&year)
+			|| second > 60
+			|| date_time.weekday().number_days_from_monday() != weekday
+		{
+			return None;
+		}
+
+		// Leap seconds are folded into the following second.
+		let leap_second = Duration::from_secs(u64::from(second.saturating_sub(59)));
+		Self::try_from(date_time.to_system_time()? + leap_second).ok()
+	}
+}
+
+impl Display for HttpDate {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		let date_time = UtcDateTime::from(SystemTime::from(*self));
+		let weekday = name(&DAYS, date_time.weekday().number_days_from_monday())?;
+		let month = name(&MONTHS, date_time.month() - 1)?;
+
+		write!(
+			formatter,
+			"{weekday}, {:02} {month} {:04} {:02}:{:02}:{:02} GMT",
+			date_time.day(),
+			date_time.year(),
+			date_time.hour(),
+			date_time.minute(),
+			date_time.second(),
+		)
+	}
+}
+
+impl FromStr for HttpDate {
+	type Err = HttpDateError;
+
+	/// See [`HttpDate::parse_with_year()`].
+	fn from_str(date: &str) -> Result<Self, Self::Err> {
+		Self::parse(date, current_year())
+	}
+}
+
+impl From<HttpDate> for SystemTime {
+	fn from(date: HttpDate) -> Self {
+		UNIX_EPOCH + Duration::from_secs(date.0)
+	}
+}
+
+impl TryFrom<SystemTime> for HttpDate {
+	type Error = HttpDateError;
+
+	/// Truncates sub-second precision. Fails if `time` is before
+	/// [`UNIX_EPOCH`] or after the year 9999.
+	fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+		let secs = time
+			.duration_since(UNIX_EPOCH)
+			.map_err(|_| HttpDateError)?
+			.as_secs();
+
+		if secs < MAX {
+			Ok(Self(secs))
+		} else {
+			Err(HttpDateError)
+		}
+	}
+}
+
+/// Error returned when converting a [`SystemTime`] to an [`HttpDate`] or
+/// parsing an [`HttpDate`] fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HttpDateError;
+
+impl Display for HttpDateError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		write!(formatter, "invalid or out of range HTTP date")
+	}
+}
+
+#[cfg(any(
+	all(target_arch = "wasm32", feature = "std"),
+	all(not(target_arch = "wasm32"), not(target_os = "none"))
+))]
+impl Error for HttpDateError {}
+
+/// HTTP date extension for [`SystemTime`].
+///
+/// On the Web with the `std` crate feature this is additionally implemented
+/// for [`std::time::SystemTime`] by converting with `web::SystemTimeExt`.
+///
+/// [`std::time::SystemTime`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html
+pub trait HttpDateExt: Sized {
+	/// Parses an HTTP date in any of the supported formats.
+	///
+	/// # Errors
+	///
+	/// If `date` isn't a valid HTTP date.
+	fn from_http_date(date: &str) -> Result<Self, HttpDateError>;
+
+	/// Converts to an [`HttpDate`], which is formatted as IMF-fixdate by
+	/// [`Display`]. Sub-second precision is truncated.
+	///
+	/// # Errors
+	///
+	/// If `self` is before [`UNIX_EPOCH`] or after the year 9999.
+	fn to_http_date(&self) -> Result<HttpDate, HttpDateError>;
+}
+
+impl HttpDateExt for SystemTime {
+	fn from_http_date(date: &str) -> Result<Self, HttpDateError> {
+		date.parse::<HttpDate>().map(Self::from)
+	}
+
+	fn to_http_date(&self) -> Result<HttpDate, HttpDateError> {
+		HttpDate::try_from(*self)
+	}
+}
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "std"
+))]
+impl HttpDateExt for StdSystemTime {
+	fn from_http_date(date: &str) -> Result<Self, HttpDateError> {
+		SystemTime::from_http_date(date).map(SystemTime::to_std)
+	}
+
+	fn to_http_date(&self) -> Result<HttpDate, HttpDateError> {
+		SystemTime::from_std(*self).to_http_date()
+	}
+}
+
+/// Returns the year of [`SystemTime::now()`].
+#[cfg(not(all(not(target_arch = "wasm32"), target_os = "none")))]
+#[allow(clippy::unnecessary_wraps)]
+fn current_year() -> Option<i64> {
+	Some(UtcDateTime::from(SystemTime::now()).year())
+}
+
+/// Returns [`None`], as a system time clock might not be registered.
+#[cfg(all(not(target_arch = "wasm32"), target_os = "none"))]
+const fn current_year() -> Option<i64> {
+	None
+}
+
+/// Returns the index of `name` in `names`.
+fn index_of(names: &[&str], name: &[u8]) -> Option<u8> {
+	names
+		.iter()
+		.zip(0..)
+		.find_map(|(candidate, index)| (candidate.as_bytes() == name).then(|| index))
+}
+
+/// Returns the name at `index` in `names`.
+fn name(names: &[&'static str], index: u8) -> Result<&'static str, fmt::Error> {
+	names.get(usize::from(index)).copied().ok_or(fmt::Error)
+}
+
+/// Parses ASCII decimal digits.
+fn digits(digits: &[u8]) -> Option<u16> {
+	digits.iter().try_fold(0, |value: u16, digit| {
+		digit
+			.is_ascii_digit()
+			.then(|| value * 10 + u16::from(digit - b'0'))
+	})
+}
@@ -20,7 +20,14 @@
 //! This library specifically targets browsers, that support
 //! [`Performance.now()`], with the `wasm32-unknown-unknown` or `wasm32v1-none`
 //! target. Emscripten is not supported. WASI doesn't require support as it has
-//! it's own native API to deal with [`std::time`].
+//! it's own native API to deal with [`std::time`]. If a WASI binary is run in
+//! a browser through a shim, the Web implementation can be enforced with the
+//! [`web_time_force_web`](#web_time_force_web) configuration.
+//!
+//! On targets without a native time source, e.g. `thumbv7em-none-eabihf` or
+//! `x86_64-unknown-none`, this library is `no_std` and the clock source has
+//! to be supplied by the user with `register_clock!`. See
+//! [#Custom clock](#custom-clock) for more information.
 //!
 //! Furthermore it depends on [`wasm-bindgen`], which is required. This library
 //! will continue to depend on it until a viable alternative presents itself, in
@@ -38,6 +45,33 @@
 //!
 //! See [the MDN documentation on this](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now#ticking_during_sleep) for more information.
 //!
+//! ## Custom clock
+//!
+//! On non-Wasm targets with `target_os = "none"` there is no [`std::time`] to
+//! re-export. Instead the same [`Instant`] and [`SystemTime`] implementation
+//! used for the Web is provided, retrieving the current time from functions
+//! registered with `register_clock!`:
+//!
+//! ```no_run
+//! # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+//! #
+//! # #[cfg(target_arch = "wasm32")]
+//! # use tests_web as _;
+//! use web_time::Duration;
+//!
+//! fn instant_now() -> Duration {
+//! 	// E.g. read a hardware timer.
+//! 	# Duration::ZERO
+//! }
+//!
+//! web_time::register_clock!(instant_now);
+//! #
+//! # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+//! # fn main() {}
+//! ```
+//!
+//! The `std` crate feature has no effect on these targets.
+//!
 //! ## Context support
 //!
 //! The implementation of [`Instant::now()`] relies on the availability of the
@@ -65,6 +99,22 @@
 //! of [`Instant::now()`] and [`SystemTime::now()`], but the vast majority of
 //! the time is still spent going through JS.
 //!
+//! # Utilities
+//!
+//! Additionally this library offers some utilities built on top of
+//! [`Instant`], which are generic over their [`Clock`] to allow manually
+//! controlling the time in tests:
+//!
+//! - [`Stopwatch`]: Measures elapsed time with support for pausing and laps.
+//! - [`Histogram`]: Records latencies and computes percentiles.
+//! - [`RateLimiter`]: Limits the rate of e.g. API calls with a token bucket or
+//!   the generic cell rate algorithm.
+//! - [`Backoff`]: Iterates over exponentially growing delays between retries,
+//!   optionally with jitter.
+//!
+//! [`Deadline`] wraps an [`Instant`] to propagate timeouts through multiple
+//! layers.
+//!
 //! # Features
 //!
 //! ## `std` (enabled by default)
@@ -74,7 +124,8 @@
 //!
 //! Without this crate feature compilation the standard library is not included.
 //! Has no effect on targets other then `wasm32-unknown-unknown` or
-//! `wasm32v1-none`.
+//! `wasm32v1-none`, unless [`web_time_force_web`](#web_time_force_web) is
+//! enabled.
 //!
 //! ## `msrv` (enabled by default)
 //!
@@ -89,10 +140,75 @@
 //!   instruction. Which will significantly reduce the instruction count for
 //!   [`Instant::now()`].
 //!
+//! ## `chrono`
+//!
+//! Implements conversions between [`SystemTime`] and [`chrono::DateTime`]
+//! identical to those Chrono provides for [`std::time::SystemTime`], except
+//! that conversions to [`SystemTime`] are implemented with [`TryFrom`], as
+//! times before [`UNIX_EPOCH`] can't be represented on the Web. Other
+//! Chrono types can be reached from there, e.g. with
+//! [`DateTime::naive_utc()`], and [`Duration`] converts with
+//! [`TimeDelta::from_std()`] and [`TimeDelta::to_std()`] on all targets.
+//! When not re-exporting [`std::time`], [`SystemTime`] additionally converts
+//! with [`chrono::NaiveDateTime`] in UTC, which Chrono doesn't provide for
+//! [`std::time::SystemTime`].
+//!
+//! ## `futures`
+//!
+//! Implements [`Stream`] for [`web::Timer`] and enables the [`web::debounce()`]
+//! and [`web::throttle()`] stream adapters. Has no effect on targets other then
+//! `wasm32-unknown-unknown` or `wasm32v1-none`.
+//!
+//! ## `jiff`
+//!
+//! Implements conversions between [`SystemTime`] and [`jiff::Timestamp`]
+//! identical to those Jiff provides for [`std::time::SystemTime`], except that
+//! conversions to [`SystemTime`] are implemented with [`TryFrom`], as times
+//! before [`UNIX_EPOCH`] can't be represented on the Web, and enables
+//! [`web::zoned_now()`] to get the current [`jiff::Zoned`] in the browsers time
+//! zone.
+//!
+//! This crate feature requires Rust v1.70.
+//!
+//! ## `profiling`
+//!
+//! Enables [`web::profiling`], which otherwise compiles to no-ops. Has no
+//! effect on targets other then `wasm32-unknown-unknown` or `wasm32v1-none`.
+//!
 //! ## `serde`
 //!
 //! Implements [`serde::Deserialize`] and [`serde::Serialize`] for
-//! [`SystemTime`].
+//! [`SystemTime`] and [`HistogramSnapshot`].
+//!
+//! ## `time`
+//!
+//! Implements conversions between [`SystemTime`] and [`time::OffsetDateTime`]
+//! or [`time::UtcDateTime`], as well as arithmetic between [`SystemTime`] or
+//! [`Instant`] and [`time::Duration`], identical to those `time` provides for
+//! [`std::time::SystemTime`] and [`std::time::Instant`], except that
+//! conversions to [`SystemTime`] are implemented with [`TryFrom`], as times
+//! before [`UNIX_EPOCH`] can't be represented on the Web. [`Duration`] converts
+//! to and from [`time::Duration`] with [`TryFrom`] on all targets.
+//!
+//! The deprecated `time::Instant` wraps [`std::time::Instant`], which is not
+//! supported on Web, and therefore can't be converted from [`Instant`].
+//!
+//! This crate feature requires Rust v1.67.
+//!
+//! ## `tokio`
+//!
+//! Enables [`tokio_compat`], which mirrors the API of [`tokio::time`] backed by
+//! the browsers timers and [`Instant`], allowing code to switch between both
+//! by swapping a single import. On targets other then `wasm32-unknown-unknown`
+//! or `wasm32v1-none` it re-exports [`tokio::time`] and requires Rust v1.63.
+//!
+//! ## `tracing`
+//!
+//! Enables [`web::tracing`], which provides timers for [`tracing-subscriber`]
+//! based on [`SystemTime`] and [`Instant`] as well as a layer reporting spans
+//! and events to the browsers performance tools. Implies `profiling`.
+//!
+//! This crate feature requires the standard library and Rust v1.65.
 //!
 //! # Conditional Configurations
 //!
@@ -101,6 +217,33 @@
 //! This requires Rust nightly and enhances the documentation. It must only be
 //! used with `RUSTDOCFLAGS`, not with `RUSTFLAGS`.
 //!
+//! ## `web_time_force_web`
+//!
+//! Uses the Web implementation on all Wasm targets instead of re-exporting
+//! [`std::time`]. This is useful when running e.g. `wasm32-wasip1` in browsers
+//! through a WASI shim with a poor implementation of `clock_time_get`.
+//! [`Instant::now()`] and [`SystemTime::now()`] will then directly use
+//! [`Performance.now()`] and [`Date.now()`].
+//!
+//! This requires [`wasm-bindgen`] and is only supported on `wasm32` targets. It
+//! must be used with `RUSTFLAGS`, e.g.:
+//!
+//! ```sh
+//! RUSTFLAGS=--cfg=web_time_force_web cargo build --target wasm32-wasip1
+//! ```
+//!
+//! ## `web_time_inline_js`
+//!
+//! [`Instant::now()`] calls into a small JS snippet instead of regular
+//! bindings, which caches the [`Performance` object] and applies the
+//! [`Performance.timeOrigin`] offset in a single call. This reduces its
+//! overhead, but JS snippets are not supported by `wasm-bindgen`'s
+//! `--target no-modules`. It must be used with `RUSTFLAGS`, e.g.:
+//!
+//! ```sh
+//! RUSTFLAGS=--cfg=web_time_inline_js cargo build --target wasm32-unknown-unknown
+//! ```
+//!
 //! # MSRV Policy
 //!
 //! The MSRV is v1.60. Changes to the MSRV will be accompanied by a minor
@@ -152,6 +295,8 @@
 //! [`SystemTime`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html
 //! [`SystemTime::now()`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html#method.now
 //! [`std::time`]: https://doc.rust-lang.org/std/time/
+//! [`std::time::Instant`]: https://doc.rust-lang.org/std/time/struct.Instant.html
+//! [`std::time::SystemTime`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html
 //! [`performance.now()`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/now
 //! [`Performance.timeOrigin`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin
 //! [`Performance` object]: https://developer.mozilla.org/en-US/docs/Web/API/performance_property
@@ -160,36 +305,151 @@
 	doc = "[`serde::Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html",
 	doc = "[`serde::Serialize`]: https://docs.rs/serde/1/serde/trait.Serialize.html"
 )]
+#![cfg_attr(
+	not(feature = "chrono"),
+	doc = "[`chrono::DateTime`]: https://docs.rs/chrono/0.4/chrono/struct.DateTime.html",
+	doc = "[`chrono::NaiveDateTime`]: https://docs.rs/chrono/0.4/chrono/struct.NaiveDateTime.html",
+	doc = "[`DateTime::naive_utc()`]: https://docs.rs/chrono/0.4/chrono/struct.DateTime.html#method.naive_utc",
+	doc = "[`TimeDelta::from_std()`]: https://docs.rs/chrono/0.4/chrono/struct.TimeDelta.html#method.from_std",
+	doc = "[`TimeDelta::to_std()`]: https://docs.rs/chrono/0.4/chrono/struct.TimeDelta.html#method.to_std"
+)]
+#![cfg_attr(
+	feature = "chrono",
+	doc = "[`DateTime::naive_utc()`]: chrono::DateTime::naive_utc",
+	doc = "[`TimeDelta::from_std()`]: chrono::TimeDelta::from_std",
+	doc = "[`TimeDelta::to_std()`]: chrono::TimeDelta::to_std"
+)]
+#![cfg_attr(
+	not(all(
+		feature = "futures",
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	)),
+	doc = "[`Stream`]: https://docs.rs/futures-core/0.3/futures_core/stream/trait.Stream.html"
+)]
+#![cfg_attr(
+	all(
+		feature = "futures",
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	),
+	doc = "[`Stream`]: futures_core::Stream"
+)]
+#![cfg_attr(
+	not(all(
+		feature = "futures",
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	)),
+	doc = "[`web::debounce()`]: https://docs.rs/web-time/1/web_time/web/fn.debounce.html",
+	doc = "[`web::throttle()`]: https://docs.rs/web-time/1/web_time/web/fn.throttle.html"
+)]
+#![cfg_attr(
+	not(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	)),
+	doc = "[`web::Timer`]: https://docs.rs/web-time/1/web_time/web/struct.Timer.html"
+)]
+#![cfg_attr(
+	not(feature = "jiff"),
+	doc = "[`jiff::Timestamp`]: https://docs.rs/jiff/0.2/jiff/struct.Timestamp.html",
+	doc = "[`jiff::Zoned`]: https://docs.rs/jiff/0.2/jiff/struct.Zoned.html",
+	doc = "[`web::zoned_now()`]: https://docs.rs/web-time/1/web_time/web/fn.zoned_now.html"
+)]
+// `time` would resolve to the `time` module of this crate.
+#![doc = "[`time::Duration`]: https://docs.rs/time/0.3/time/struct.Duration.html"]
+#![doc = "[`time::OffsetDateTime`]: https://docs.rs/time/0.3/time/struct.OffsetDateTime.html"]
+#![doc = "[`time::UtcDateTime`]: https://docs.rs/time/0.3/time/struct.UtcDateTime.html"]
+#![cfg_attr(
+	not(feature = "tokio"),
+	doc = "[`tokio_compat`]: https://docs.rs/web-time/1/web_time/tokio_compat/index.html"
+)]
+#![cfg_attr(
+	any(
+		not(feature = "tokio"),
+		all(
+			target_arch = "wasm32",
+			any(target_os = "unknown", target_os = "none", web_time_force_web)
+		),
+		all(not(target_arch = "wasm32"), target_os = "none")
+	),
+	doc = "[`tokio::time`]: https://docs.rs/tokio/1/tokio/time/index.html"
+)]
+#![cfg_attr(
+	not(feature = "tracing"),
+	doc = "[`web::tracing`]: https://docs.rs/web-time/1/web_time/web/tracing/index.html"
+)]
+//! [`tracing-subscriber`]: https://crates.io/crates/tracing-subscriber
 //! [`wasm-bindgen`]: https://crates.io/crates/wasm-bindgen
 //! [`f64.nearest`]: https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-numeric
 //! [`f64.trunc`]: https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-numeric
 
-#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+#![cfg_attr(
+	any(
+		all(target_arch = "wasm32", not(feature = "std")),
+		all(not(target_arch = "wasm32"), target_os = "none")
+	),
+	no_std
+)]
 #![cfg_attr(all(test, target_arch = "wasm32"), no_main)]
 #![cfg_attr(all(doc, docsrs), feature(doc_cfg))]
 #![cfg_attr(all(not(feature = "std"), nightly), feature(asm_experimental_arch))]
 
-#[cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
-mod time;
+mod backoff;
+mod clock;
+mod deadline;
+#[cfg(target_has_atomic = "64")]
+mod histogram;
+pub mod httpdate;
+mod rate_limiter;
+mod stopwatch;
 #[cfg(any(
 	all(
 		target_arch = "wasm32",
-		any(target_os = "unknown", target_os = "none"),
-		feature = "std"
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
 	),
-	all(doc, docsrs)
+	all(not(target_arch = "wasm32"), target_os = "none")
 ))]
-#[cfg_attr(all(doc, docsrs), doc(cfg(all(Web, feature = "std"))))]
+mod time;
+#[cfg(all(
+	feature = "tokio",
+	any(target_arch = "wasm32", not(target_os = "none"))
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "tokio")))]
+pub mod tokio_compat;
+mod utc;
 pub mod web;
 
-#[cfg(not(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none"))))]
+#[cfg(not(any(
+	all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	),
+	all(not(target_arch = "wasm32"), target_os = "none")
+)))]
 pub use std::time::*;
 
 #[cfg(all(test, target_arch = "wasm32"))]
 use tests_web as _;
 
-#[cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
+pub use self::backoff::{Backoff, DefaultRandom, RandomSource};
+pub use self::clock::{Clock, DefaultClock};
+pub use self::deadline::Deadline;
+#[cfg(target_has_atomic = "64")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(target_has_atomic = "64")))]
+pub use self::histogram::{Histogram, HistogramSnapshot};
+pub use self::rate_limiter::{Quota, RateLimiter};
+pub use self::stopwatch::Stopwatch;
+#[cfg(any(
+	all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	),
+	all(not(target_arch = "wasm32"), target_os = "none")
+))]
 pub use self::time::*;
+pub use self::utc::{UtcDateTime, Weekday};
 
 #[cfg(all(not(doc), docsrs))]
 compile_error!("`--cfg docsrs` must only be used via `RUSTDOCFLAGS`, not `RUSTFLAGS`");
@@ -0,0 +1,325 @@
+//! Rate limiter with token bucket and GCRA modes.
+
+use core::num::NonZeroU32;
+use core::time::Duration;
+
+use crate::clock::{Clock, DefaultClock};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+use crate::web::Timer;
+use crate::Instant;
+
+/// Rate limiter allowing a number of cells, e.g. API calls or input events,
+/// per period.
+///
+/// The behavior is determined by its [`Quota`]:
+/// - [`Quota::token_bucket()`] refills a number of tokens at once every period.
+/// - [`Quota::gcra()`] implements the [generic cell rate algorithm], which
+///   spaces cells evenly over the period while still allowing a burst.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use core::num::NonZeroU32;
+///
+/// use web_time::{Duration, Quota, RateLimiter};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// // 10 calls per second, up to 5 at once.
+/// let quota = Quota::gcra(
+/// 	NonZeroU32::new(10).unwrap(),
+/// 	Duration::from_secs(1),
+/// 	NonZeroU32::new(5).unwrap(),
+/// );
+/// let mut limiter = RateLimiter::new(quota);
+///
+/// for _ in 0..5 {
+/// 	assert!(limiter.check());
+/// }
+///
+/// assert!(!limiter.check());
+/// assert!(limiter.until_ready() <= Duration::from_millis(100));
+/// # }
+/// ```
+///
+/// [generic cell rate algorithm]: https://en.wikipedia.org/wiki/Generic_cell_rate_algorithm
+#[derive(Clone, Debug)]
+pub struct RateLimiter<C = DefaultClock> {
+	/// Time source.
+	clock: C,
+	/// Configured limits.
+	quota: Quota,
+	/// Current state.
+	state: State,
+}
+
+/// State of a [`RateLimiter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+	/// State of [`Quota::token_bucket()`].
+	TokenBucket {
+		/// Available tokens.
+		tokens: u32,
+		/// Last time tokens were refilled.
+		refilled: Instant,
+	},
+	/// State of [`Quota::gcra()`].
+	Gcra {
+		/// Theoretical arrival time of the next cell, [`None`] if it overflowed
+		/// [`Instant`].
+		arrival: Option<Instant>,
+	},
+}
+
+/// Limits of a [`RateLimiter`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Quota(Mode);
+
+/// Mode of a [`Quota`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Mode {
+	/// See [`Quota::token_bucket()`].
+	TokenBucket {
+		/// Maximum number of tokens.
+		capacity: u32,
+		/// Number of tokens refilled every `period`.
+		refill: u32,
+		/// Time between refills.
+		period: Duration,
+	},
+	/// See [`Quota::gcra()`].
+	Gcra {
+		/// Time between evenly spaced cells.
+		interval: Duration,
+		/// How far ahead of their theoretical arrival time cells are allowed.
+		tolerance: Duration,
+	},
+}
+
+impl Quota {
+	/// Creates a [`Quota`] for a token bucket starting with `capacity` tokens
+	/// and adding `refill` tokens at once every `period`, up to `capacity`.
+	///
+	/// A zero `period` doesn't limit the rate at all.
+	#[must_use]
+	pub const fn token_bucket(capacity: NonZeroU32, refill: NonZeroU32, period: Duration) -> Self {
+		Self(Mode::TokenBucket {
+			capacity: capacity.get(),
+			refill: refill.get(),
+			period,
+		})
+	}
+
+	/// Creates a [`Quota`] for the [generic cell rate algorithm] allowing
+	/// `rate` cells evenly spaced over `period`, of which up to `burst` can
+	/// happen at once.
+	///
+	/// A zero `period` doesn't limit the rate at all.
+	///
+	/// [generic cell rate algorithm]: https://en.wikipedia.org/wiki/Generic_cell_rate_algorithm
+	#[must_use]
+	pub fn gcra(rate: NonZeroU32, period: Duration, burst: NonZeroU32) -> Self {
+		let interval = period / rate.get();
+
+		Self(Mode::Gcra {
+			interval,
+			tolerance: interval
+				.checked_mul(burst.get() - 1)
+				.unwrap_or(Duration::MAX),
+		})
+	}
+}
+
+impl RateLimiter {
+	/// Creates a new [`RateLimiter`] with the given [`Quota`], allowing a full
+	/// burst right away.
+	#[must_use]
+	pub fn new(quota: Quota) -> Self {
+		Self::with_clock(DefaultClock, quota)
+	}
+}
+
+impl<C> RateLimiter<C> {
+	/// Returns the [`Clock`] used by this [`RateLimiter`].
+	pub const fn clock(&self) -> &C {
+		&self.clock
+	}
+
+	/// Returns the [`Quota`] of this [`RateLimiter`].
+	#[must_use]
+	pub const fn quota(&self) -> Quota {
+		self.quota
+	}
+}
+
+impl<C: Clock> RateLimiter<C> {
+	/// Creates a new [`RateLimiter`] with the given [`Quota`], allowing a full
+	/// burst right away, using the given [`Clock`].
+	pub fn with_clock(clock: C, quota: Quota) -> Self {
+		let state = State::new(quota, clock.now());
+
+		Self {
+			clock,
+			quota,
+			state,
+		}
+	}
+
+	/// Returns [`true`] and consumes a cell if the [`Quota`] allows it now.
+	pub fn check(&mut self) -> bool {
+		let now = self.clock.now();
+
+		match (self.quota.0, &mut self.state) {
+			(
+				Mode::TokenBucket {
+					capacity,
+					refill,
+					period,
+				},
+				State::TokenBucket { tokens, refilled },
+			) => {
+				let (available, last) =
+					refill_at(*tokens, *refilled, capacity, refill, period, now);
+				*refilled = last;
+
+				if let Some(available) = available.checked_sub(1) {
+					*tokens = available;
+					true
+				} else {
+					*tokens = 0;
+					false
+				}
+			}
+			(
+				Mode::Gcra {
+					interval,
+					tolerance,
+				},
+				State::Gcra { arrival },
+			) => match *arrival {
+				Some(theoretical) if is_early(theoretical, tolerance, now) => false,
+				Some(theoretical) => {
+					*arrival = theoretical.max(now).checked_add(interval);
+					true
+				}
+				None => false,
+			},
+			_ => unreachable!("found mismatched `Quota` and state"),
+		}
+	}
+
+	/// Returns the time until [`check()`](Self::check) will succeed, which is
+	/// zero if it will succeed now.
+	///
+	/// Returns [`Duration::MAX`] if it will never succeed again.
+	#[must_use]
+	pub fn until_ready(&self) -> Duration {
+		let now = self.clock.now();
+
+		match (self.quota.0, self.state) {
+			(
+				Mode::TokenBucket {
+					capacity,
+					refill,
+					period,
+				},
+				State::TokenBucket { tokens, refilled },
+			) => match refill_at(tokens, refilled, capacity, refill, period, now) {
+				(0, last) => last
+					.checked_add(period)
+					.map_or(Duration::MAX, |next| next.saturating_duration_since(now)),
+				_ => Duration::ZERO,
+			},
+			(Mode::Gcra { tolerance, .. }, State::Gcra { arrival }) => match arrival {
+				Some(theoretical) => now.checked_add(tolerance).map_or(Duration::ZERO, |limit| {
+					theoretical.saturating_duration_since(limit)
+				}),
+				None => Duration::MAX,
+			},
+			_ => unreachable!("found mismatched `Quota` and state"),
+		}
+	}
+
+	/// Restores the full burst of the [`Quota`] now.
+	pub fn reset(&mut self) {
+		self.state = State::new(self.quota, self.clock.now());
+	}
+
+	/// Waits until [`check()`](Self::check) succeeds.
+	///
+	/// Waits with [`Timer`], so a manually controlled
+	/// [`Clock`] has to be advanced in the meantime. Never completes if the
+	/// next cell can't be allowed before [`Instant`] overflows.
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	))]
+	#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+	pub async fn acquire(&mut self) {
+		while !self.check() {
+			Timer::after(self.until_ready()).await;
+		}
+	}
+}
+
+impl State {
+	/// Creates the initial [`State`] for `quota` at `now`.
+	const fn new(quota: Quota, now: Instant) -> Self {
+		match quota.0 {
+			Mode::TokenBucket { capacity, .. } => Self::TokenBucket {
+				tokens: capacity,
+				refilled: now,
+			},
+			Mode::Gcra { .. } => Self::Gcra { arrival: Some(now) },
+		}
+	}
+}
+
+/// Returns the available tokens and the time of the last refill at `now`.
+fn refill_at(
+	tokens: u32,
+	refilled: Instant,
+	capacity: u32,
+	refill: u32,
+	period: Duration,
+	now: Instant,
+) -> (u32, Instant) {
+	if period.is_zero() {
+		return (capacity, now);
+	}
+
+	let periods = now.saturating_duration_since(refilled).as_nanos() / period.as_nanos();
+	let periods = u32::try_from(periods).unwrap_or(u32::MAX);
+
+	if periods == 0 {
+		return (tokens, refilled);
+	}
+
+	let tokens = tokens.saturating_add(periods.saturating_mul(refill));
+
+	if tokens >= capacity {
+		// A full bucket starts its next period when a token is taken again.
+		(capacity, now)
+	} else {
+		// Keep refilling at multiples of `period`.
+		let refilled = period
+			.checked_mul(periods)
+			.and_then(|elapsed| refilled.checked_add(elapsed))
+			.unwrap_or(now);
+		(tokens, refilled)
+	}
+}
+
+/// Returns [`true`] if a cell at `now` arrives too early for its `theoretical`
+/// arrival time.
+fn is_early(theoretical: Instant, tolerance: Duration, now: Instant) -> bool {
+	now.checked_add(tolerance)
+		.map_or(false, |limit| theoretical > limit)
+}
@@ -0,0 +1,219 @@
+//! Stopwatch measuring elapsed time excluding paused periods.
+
+use core::fmt::{self, Display, Formatter};
+use core::time::Duration;
+
+use crate::clock::{Clock, DefaultClock};
+use crate::Instant;
+
+/// Stopwatch with support for pausing and laps.
+///
+/// The elapsed time excludes any time the stopwatch was paused. It is
+/// [`Display`]ed like the [`Debug`](fmt::Debug) output of [`Duration`],
+/// including support for precision, e.g. `{:.3}`.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::Stopwatch;
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let mut stopwatch = Stopwatch::start_new();
+/// // Do some work.
+/// let first = stopwatch.lap();
+///
+/// stopwatch.pause();
+/// // Not measured.
+/// stopwatch.resume();
+///
+/// // Do some more work.
+/// let second = stopwatch.lap();
+/// let total = stopwatch.stop();
+/// assert!(total >= first + second);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Stopwatch<C = DefaultClock> {
+	/// Time source.
+	clock: C,
+	/// Current state.
+	state: State,
+	/// Elapsed time accumulated before the current running period.
+	accumulated: Duration,
+	/// Elapsed time at the end of the last lap.
+	lap_end: Duration,
+	/// Number of completed laps.
+	laps: u32,
+}
+
+/// State of a [`Stopwatch`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+	/// Not started yet or reset.
+	Idle,
+	/// Running since the given [`Instant`].
+	Running(Instant),
+	/// Paused, can be resumed.
+	Paused,
+	/// Stopped, has to be started again.
+	Stopped,
+}
+
+impl Stopwatch {
+	/// Creates a new [`Stopwatch`] that isn't running yet.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self::with_clock(DefaultClock)
+	}
+
+	/// Creates a new [`Stopwatch`] and [`start()`](Self::start)s it.
+	#[must_use]
+	pub fn start_new() -> Self {
+		let mut stopwatch = Self::new();
+		stopwatch.start();
+		stopwatch
+	}
+}
+
+impl Default for Stopwatch {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<C> Stopwatch<C> {
+	/// Creates a new [`Stopwatch`] that isn't running yet, using the given
+	/// [`Clock`].
+	#[must_use]
+	pub const fn with_clock(clock: C) -> Self {
+		Self {
+			clock,
+			state: State::Idle,
+			accumulated: Duration::ZERO,
+			lap_end: Duration::ZERO,
+			laps: 0,
+		}
+	}
+
+	/// Returns the [`Clock`] used by this [`Stopwatch`].
+	pub const fn clock(&self) -> &C {
+		&self.clock
+	}
+
+	/// Returns [`true`] if this [`Stopwatch`] is running.
+	#[must_use]
+	pub const fn is_running(&self) -> bool {
+		matches!(self.state, State::Running(_))
+	}
+
+	/// Returns [`true`] if this [`Stopwatch`] is paused.
+	#[must_use]
+	pub const fn is_paused(&self) -> bool {
+		matches!(self.state, State::Paused)
+	}
+
+	/// Returns [`true`] if this [`Stopwatch`] is stopped.
+	#[must_use]
+	pub const fn is_stopped(&self) -> bool {
+		matches!(self.state, State::Stopped)
+	}
+
+	/// Returns the number of completed laps.
+	#[must_use]
+	pub const fn laps(&self) -> u32 {
+		self.laps
+	}
+
+	/// Resets this [`Stopwatch`] to its initial state, discarding the elapsed
+	/// time and all laps.
+	pub fn reset(&mut self) {
+		self.state = State::Idle;
+		self.accumulated = Duration::ZERO;
+		self.lap_end = Duration::ZERO;
+		self.laps = 0;
+	}
+}
+
+impl<C: Clock> Stopwatch<C> {
+	/// Creates a new [`Stopwatch`] using the given [`Clock`] and
+	/// [`start()`](Self::start)s it.
+	#[must_use]
+	pub fn start_with_clock(clock: C) -> Self {
+		let mut stopwatch = Self::with_clock(clock);
+		stopwatch.start();
+		stopwatch
+	}
+
+	/// Resets and starts this [`Stopwatch`].
+	pub fn start(&mut self) {
+		self.reset();
+		self.state = State::Running(self.clock.now());
+	}
+
+	/// Stops this [`Stopwatch`] and returns the total elapsed time.
+	///
+	/// A stopped [`Stopwatch`] can't be [`resume()`](Self::resume)d and
+	/// has to be [`start()`](Self::start)ed again, which resets it.
+	pub fn stop(&mut self) -> Duration {
+		self.pause();
+		self.state = State::Stopped;
+		self.accumulated
+	}
+
+	/// Pauses this [`Stopwatch`] until [`resume()`](Self::resume) is called.
+	///
+	/// Has no effect if the [`Stopwatch`] isn't running.
+	pub fn pause(&mut self) {
+		if let State::Running(since) = self.state {
+			self.accumulated += self.clock.now().saturating_duration_since(since);
+			self.state = State::Paused;
+		}
+	}
+
+	/// Resumes this [`Stopwatch`] after it was [`pause()`](Self::pause)d.
+	///
+	/// Has no effect if the [`Stopwatch`] isn't paused.
+	pub fn resume(&mut self) {
+		if let State::Paused = self.state {
+			self.state = State::Running(self.clock.now());
+		}
+	}
+
+	/// Returns the total elapsed time, excluding paused periods.
+	#[must_use]
+	pub fn elapsed(&self) -> Duration {
+		match self.state {
+			State::Running(since) => {
+				self.accumulated + self.clock.now().saturating_duration_since(since)
+			}
+			State::Idle | State::Paused | State::Stopped => self.accumulated,
+		}
+	}
+
+	/// Returns the elapsed time of the current lap, excluding paused periods.
+	#[must_use]
+	pub fn current_lap(&self) -> Duration {
+		self.elapsed().saturating_sub(self.lap_end)
+	}
+
+	/// Completes the current lap and returns its elapsed time, excluding
+	/// paused periods. The next lap starts immediately.
+	pub fn lap(&mut self) -> Duration {
+		let elapsed = self.elapsed();
+		let lap = elapsed.saturating_sub(self.lap_end);
+		self.lap_end = elapsed;
+		self.laps = self.laps.saturating_add(1);
+		lap
+	}
+}
+
+impl<C: Clock> Display for Stopwatch<C> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(&self.elapsed(), f)
+	}
+}
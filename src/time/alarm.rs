@@ -0,0 +1,322 @@
+//! Waking tasks at an [`Instant`] with [`setTimeout()`].
+//!
+//! All pending deadlines of a thread are kept in a single queue, which only
+//! schedules a `setTimeout()` for the earliest one. When it fires, all tasks
+//! with a reached deadline are woken and the next `setTimeout()` is scheduled.
+//!
+//! Without the `std` crate feature there is no thread-local storage for the
+//! queue, so every deadline schedules its own `setTimeout()` instead.
+//!
+//! [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+
+#[cfg(feature = "std")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+#[cfg(feature = "std")]
+use core::mem;
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use wasm_bindgen::closure::Closure;
+#[cfg(feature = "std")]
+use wasm_bindgen::JsValue;
+
+use super::{js, Instant};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+	/// Queue of this thread.
+	static QUEUE: RefCell<Queue> = const { RefCell::new(Queue::new()) };
+}
+
+/// Calls `fun` with the [`Queue`] of this thread.
+#[cfg(feature = "std")]
+fn with<R, F: FnOnce(&mut Queue) -> R>(fun: F) -> R {
+	QUEUE.with(|queue| fun(&mut queue.borrow_mut()))
+}
+
+/// Identifies an entry in the [`Queue`].
+#[cfg(feature = "std")]
+type Key = (Instant, u64);
+
+/// ID of the next entry. Shared between all threads, so an [`Alarm`] sent to
+/// another thread can't remove an unrelated entry there.
+#[cfg(feature = "std")]
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Pending deadlines of a thread.
+#[cfg(feature = "std")]
+struct Queue {
+	/// Entries sorted by their deadline.
+	entries: Vec<(Key, Waker)>,
+	/// Currently scheduled `setTimeout()`.
+	scheduled: Option<Scheduled>,
+	/// Callback passed to `setTimeout()`.
+	closure: Option<Closure<dyn FnMut()>>,
+}
+
+/// A scheduled `setTimeout()`.
+#[cfg(feature = "std")]
+struct Scheduled {
+	/// The deadline it was scheduled for.
+	deadline: Instant,
+	/// Handle returned by `setTimeout()`.
+	handle: JsValue,
+}
+
+#[cfg(feature = "std")]
+impl Queue {
+	/// Creates an empty [`Queue`].
+	const fn new() -> Self {
+		Self {
+			entries: Vec::new(),
+			scheduled: None,
+			closure: None,
+		}
+	}
+
+	/// Makes sure `waker` is woken when `deadline` is reached. Returns the
+	/// replaced [`Waker`], which should only be dropped after the [`Queue`] is
+	/// released.
+	fn register(
+		&mut self,
+		key: &mut Option<Key>,
+		deadline: Instant,
+		waker: &Waker,
+	) -> Option<Waker> {
+		if let Some(current) = *key {
+			if current.0 == deadline {
+				if let Ok(index) = self.search(current) {
+					if let Some((_, entry)) = self.entries.get_mut(index) {
+						return if entry.will_wake(waker) {
+							None
+						} else {
+							Some(mem::replace(entry, waker.clone()))
+						};
+					}
+				}
+			}
+		}
+
+		let old = key.take().and_then(|current| self.remove(current));
+		*key = Some(self.insert(deadline, waker.clone()));
+
+		old
+	}
+
+	/// Moves the entry with the given [`Key`] to `deadline`, keeping its
+	/// [`Waker`].
+	fn reset(&mut self, key: &mut Option<Key>, deadline: Instant) {
+		if let Some(waker) = key.take().and_then(|current| self.remove(current)) {
+			*key = Some(self.insert(deadline, waker));
+		}
+	}
+
+	/// Inserts a new entry and schedules a `setTimeout()` if necessary.
+	fn insert(&mut self, deadline: Instant, waker: Waker) -> Key {
+		let key = (deadline, NEXT_ID.fetch_add(1, Ordering::Relaxed));
+		let index = self.search(key).unwrap_or_else(|index| index);
+		self.entries.insert(index, (key, waker));
+
+		self.schedule();
+
+		key
+	}
+
+	/// Removes the entry with the given [`Key`]. Returns the removed
+	/// [`Waker`], which should only be dropped after the [`Queue`] is
+	/// released.
+	fn remove(&mut self, key: Key) -> Option<Waker> {
+		let index = self.search(key).ok()?;
+		Some(self.entries.remove(index).1)
+	}
+
+	/// Finds the index of the given [`Key`].
+	fn search(&self, key: Key) -> Result<usize, usize> {
+		self.entries.binary_search_by(|(entry, _)| entry.cmp(&key))
+	}
+
+	/// Schedules a `setTimeout()` if the earliest deadline isn't covered yet.
+	fn schedule(&mut self) {
+		let deadline = match self.entries.first() {
+			Some(((deadline, _), _)) => *deadline,
+			None => return,
+		};
+
+		if let Some(scheduled) = &self.scheduled {
+			// A `setTimeout()` firing before the earliest deadline will simply
+			// schedule again.
+			if scheduled.deadline <= deadline {
+				return;
+			}
+
+			js::clear_timeout(&scheduled.handle);
+		}
+
+		let closure = self.closure.get_or_insert_with(|| Closure::new(fire));
+		let handle = js::set_timeout(closure, timeout(deadline));
+		self.scheduled = Some(Scheduled { deadline, handle });
+	}
+}
+
+/// Returns the `setTimeout()` delay in milliseconds until `deadline`.
+fn timeout(deadline: Instant) -> i32 {
+	let remaining = deadline.saturating_duration_since(Instant::now());
+	// Round up to the next millisecond to not wake up too early.
+	let timeout = remaining.as_nanos().saturating_add(999_999) / 1_000_000;
+	i32::try_from(timeout).unwrap_or(i32::MAX)
+}
+
+/// Called by `setTimeout()`. Wakes all tasks whose deadline was reached.
+#[cfg(feature = "std")]
+fn fire() {
+	let wakers = with(|queue| {
+		queue.scheduled = None;
+
+		let now = Instant::now();
+		let reached = queue
+			.entries
+			.iter()
+			.take_while(|((deadline, _), _)| *deadline <= now)
+			.count();
+		let wakers: Vec<_> = queue
+			.entries
+			.drain(..reached)
+			.map(|(_, waker)| waker)
+			.collect();
+
+		queue.schedule();
+
+		wakers
+	});
+
+	for waker in wakers {
+		waker.wake();
+	}
+}
+
+/// Wakes the last polling task once a deadline is reached.
+///
+/// `setTimeout()` only has millisecond precision and may fire late, so
+/// [`Instant::now()`] is always consulted when polling.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub(crate) struct Alarm(Option<Key>);
+
+#[cfg(feature = "std")]
+impl Alarm {
+	/// Creates a new [`Alarm`] without a registered deadline.
+	pub(crate) const fn new() -> Self {
+		Self(None)
+	}
+
+	/// Returns [`Poll::Ready`] if `deadline` was reached, otherwise ensures
+	/// that the [`Waker`] in `cx` is woken when it is.
+	pub(crate) fn poll(&mut self, deadline: Instant, cx: &Context<'_>) -> Poll<()> {
+		if deadline <= Instant::now() {
+			self.cancel();
+			return Poll::Ready(());
+		}
+
+		let old = with(|queue| queue.register(&mut self.0, deadline, cx.waker()));
+		drop(old);
+
+		Poll::Pending
+	}
+
+	/// Moves the registered deadline, if any, to `deadline`, making sure the
+	/// last polling task is woken without polling again.
+	pub(crate) fn reset(&mut self, deadline: Instant) {
+		if let Some(key) = self.0 {
+			if key.0 != deadline {
+				with(|queue| queue.reset(&mut self.0, deadline));
+			}
+		}
+	}
+
+	/// Removes the registered deadline, if any.
+	pub(crate) fn cancel(&mut self) {
+		if let Some(key) = self.0.take() {
+			let old = with(|queue| queue.remove(key));
+			drop(old);
+		}
+	}
+}
+
+/// Wakes the last polling task once a deadline is reached.
+///
+/// Every registered deadline schedules its own `setTimeout()`, which can't be
+/// cleared because it isn't tied to a thread. Outdated ones only cause
+/// spurious wake-ups.
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Default)]
+pub(crate) struct Alarm(Option<(Instant, Waker)>);
+
+#[cfg(not(feature = "std"))]
+impl Alarm {
+	/// Creates a new [`Alarm`] without a registered deadline.
+	pub(crate) const fn new() -> Self {
+		Self(None)
+	}
+
+	/// Returns [`Poll::Ready`] if `deadline` was reached, otherwise ensures
+	/// that the [`Waker`] in `cx` is woken when it is.
+	pub(crate) fn poll(&mut self, deadline: Instant, cx: &Context<'_>) -> Poll<()> {
+		if deadline <= Instant::now() {
+			self.cancel();
+			return Poll::Ready(());
+		}
+
+		if let Some((current, waker)) = &self.0 {
+			if *current == deadline && waker.will_wake(cx.waker()) {
+				return Poll::Pending;
+			}
+		}
+
+		schedule(deadline, cx.waker().clone());
+		self.0 = Some((deadline, cx.waker().clone()));
+
+		Poll::Pending
+	}
+
+	/// Moves the registered deadline, if any, to `deadline`, making sure the
+	/// last polling task is woken without polling again.
+	pub(crate) fn reset(&mut self, deadline: Instant) {
+		if let Some((current, waker)) = &mut self.0 {
+			if *current != deadline {
+				*current = deadline;
+				schedule(deadline, waker.clone());
+			}
+		}
+	}
+
+	/// Removes the registered deadline, if any.
+	pub(crate) fn cancel(&mut self) {
+		self.0 = None;
+	}
+}
+
+/// Schedules a `setTimeout()` waking `waker` when `deadline` is reached.
+#[cfg(not(feature = "std"))]
+fn schedule(deadline: Instant, waker: Waker) {
+	let closure = Closure::once_into_js(move || {
+		// `setTimeout()` may fire before `Instant::now()` reaches `deadline`.
+		if deadline <= Instant::now() {
+			waker.wake();
+		} else {
+			schedule(deadline, waker);
+		}
+	});
+	js::set_timeout_once(&closure, timeout(deadline));
+}
+
+impl Drop for Alarm {
+	fn drop(&mut self) {
+		self.cancel();
+	}
+}
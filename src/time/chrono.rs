@@ -0,0 +1,109 @@
+//! Conversions between `SystemTime` and [`chrono::DateTime`]. They aim to be
+//! identical to Chrono's implementation for [`std::time::SystemTime`], except
+//! that conversions to `SystemTime` are fallible, because it can't represent
+//! times before [`UNIX_EPOCH`] on the Web.
+//! Additionally `SystemTime` converts with [`NaiveDateTime`] in UTC.
+//!
+//! This implementation was adapted from Chrono's
+//! [`From` implementations](https://github.com/chronotope/chrono/blob/v0.4.45/src/datetime/mod.rs#L1919-L1953).
+
+#[cfg(all(all(doc, docsrs), not(all(target_arch = "wasm32", feature = "std"))))]
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
+use std::error::Error;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+use super::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<SystemTime> for DateTime<Utc> {
+	/// # Panics
+	///
+	/// If `time` is out of range for [`DateTime`].
+	fn from(time: SystemTime) -> Self {
+		// CHANGED: Replaced `as` conversions with `try_from()`.
+		let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+			Ok(duration) => (
+				i64::try_from(duration.as_secs()).expect("timestamp out of range"),
+				duration.subsec_nanos(),
+			),
+			Err(error) => {
+				let duration = error.duration();
+				let secs = i64::try_from(duration.as_secs()).expect("timestamp out of range");
+				let nanos = duration.subsec_nanos();
+
+				if nanos == 0 {
+					(-secs, 0)
+				} else {
+					(-secs - 1, 1_000_000_000 - nanos)
+				}
+			}
+		};
+
+		Utc.timestamp_opt(secs, nanos).unwrap()
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for SystemTime {
+	type Error = OutOfRangeError;
+
+	/// Fails if `date_time` can't be represented by `SystemTime`, e.g. if it is
+	/// before [`UNIX_EPOCH`] on the Web.
+	fn try_from(date_time: DateTime<Tz>) -> Result<Self, Self::Error> {
+		let secs = date_time.timestamp();
+		let nanos = date_time.timestamp_subsec_nanos();
+
+		// CHANGED: Replaced `as` conversion with `unsigned_abs()` and panicking
+		// arithmetic with `checked_*()`.
+		if secs < 0 {
+			UNIX_EPOCH
+				.checked_sub(Duration::new(secs.unsigned_abs(), 0))
+				.and_then(|time| time.checked_add(Duration::new(0, nanos)))
+		} else {
+			UNIX_EPOCH.checked_add(Duration::new(secs.unsigned_abs(), nanos))
+		}
+		.ok_or(OutOfRangeError)
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<SystemTime> for NaiveDateTime {
+	/// Returns the date and time in UTC.
+	///
+	/// # Panics
+	///
+	/// If `time` is out of range for [`NaiveDateTime`].
+	fn from(time: SystemTime) -> Self {
+		DateTime::<Utc>::from(time).naive_utc()
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<NaiveDateTime> for SystemTime {
+	type Error = OutOfRangeError;
+
+	/// Interprets `date_time` as UTC. Fails if it can't be represented by
+	/// `SystemTime`, e.g. if it is before [`UNIX_EPOCH`] on the Web.
+	fn try_from(date_time: NaiveDateTime) -> Result<Self, Self::Error> {
+		Self::try_from(Utc.from_utc_datetime(&date_time))
+	}
+}
+
+/// Error returned when converting a [`DateTime`] or [`NaiveDateTime`] that
+/// can't be represented by [`SystemTime`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+pub struct OutOfRangeError;
+
+impl Display for OutOfRangeError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		write!(formatter, "date and time out of range for `SystemTime`")
+	}
+}
+
+#[cfg(any(all(target_arch = "wasm32", feature = "std"), all(doc, docsrs)))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "std")))]
+impl Error for OutOfRangeError {}
@@ -0,0 +1,39 @@
+//! Clock source registered by the user on targets without a native time
+//! source.
+//!
+//! See [`register_clock!`](crate::register_clock).
+
+use core::time::Duration;
+
+#[allow(unsafe_code)]
+extern "Rust" {
+	/// Monotonic clock registered by
+	/// [`register_clock!`](crate::register_clock).
+	fn __web_time_instant_now() -> Duration;
+
+	/// System time clock registered by
+	/// [`register_clock!`](crate::register_clock).
+	fn __web_time_system_time_now() -> Duration;
+}
+
+/// Calls the monotonic clock registered by
+/// [`register_clock!`](crate::register_clock).
+pub(super) fn instant_now() -> Duration {
+	// SAFETY: The symbol is only defined by `register_clock!`, which guarantees
+	// the correct signature.
+	#[allow(unsafe_code)]
+	unsafe {
+		__web_time_instant_now()
+	}
+}
+
+/// Calls the system time clock registered by
+/// [`register_clock!`](crate::register_clock).
+pub(super) fn system_time_now() -> Duration {
+	// SAFETY: The symbol is only defined by `register_clock!`, which guarantees
+	// the correct signature.
+	#[allow(unsafe_code)]
+	unsafe {
+		__web_time_system_time_now()
+	}
+}
@@ -10,8 +10,17 @@
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 use core::time::Duration;
 
+#[cfg(not(target_arch = "wasm32"))]
+use super::clock;
+#[cfg(all(target_arch = "wasm32", web_time_inline_js))]
+use super::js;
+#[cfg(all(target_arch = "wasm32", not(web_time_inline_js)))]
 use super::js::PERFORMANCE;
-#[cfg(target_feature = "atomics")]
+#[cfg(all(
+	target_arch = "wasm32",
+	target_feature = "atomics",
+	any(not(web_time_inline_js), feature = "profiling")
+))]
 use super::js::TIME_ORIGIN;
 
 /// See [`std::time::Instant`].
@@ -38,7 +47,15 @@ impl Instant {
 		doc = "[`std::time::Instant::now()`]: https://doc.rust-lang.org/std/time/struct.Instant.html#method.now"
 	)]
 	#[must_use]
+	#[cfg(target_arch = "wasm32")]
 	pub fn now() -> Self {
+		#[cfg(web_time_inline_js)]
+		let now = {
+			let now = js::performance_now();
+			assert!(!now.is_nan(), "`Performance` object not found");
+			now
+		};
+		#[cfg(not(web_time_inline_js))]
 		let now = PERFORMANCE.with(|performance| {
 			let performance = performance
 				.as_ref()
@@ -57,6 +74,38 @@ impl Instant {
 		Self(time_stamp_to_duration(now))
 	}
 
+	/// See [`std::time::Instant::now()`].
+	///
+	/// # Note
+	///
+	/// The time is retrieved from the clock registered with
+	/// [`register_clock!`](crate::register_clock), which is expected to be
+	/// monotonic.
+	#[cfg_attr(
+		not(feature = "std"),
+		doc = "",
+		doc = "[`std::time::Instant::now()`]: https://doc.rust-lang.org/std/time/struct.Instant.html#method.now"
+	)]
+	#[must_use]
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn now() -> Self {
+		Self(clock::instant_now())
+	}
+
+	/// Converts this [`Instant`] back to a `DOMHighResTimeStamp` relative to
+	/// the [`Performance.timeOrigin`] of the current context.
+	///
+	/// [`Performance.timeOrigin`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin
+	#[cfg(all(target_arch = "wasm32", feature = "profiling"))]
+	pub(crate) fn to_time_stamp(self) -> f64 {
+		let time_stamp = self.0.as_secs_f64() * 1000.;
+
+		#[cfg(not(target_feature = "atomics"))]
+		return time_stamp;
+		#[cfg(target_feature = "atomics")]
+		TIME_ORIGIN.with(|origin| time_stamp - origin)
+	}
+
 	/// See [`std::time::Instant::duration_since()`].
 	#[cfg_attr(
 		not(feature = "std"),
@@ -169,6 +218,7 @@ impl SubAssign<Duration> for Instant {
 }
 
 /// Converts a `DOMHighResTimeStamp` to a [`Duration`].
+#[cfg(target_arch = "wasm32")]
 ///
 /// # Note
 ///
@@ -187,9 +237,11 @@ fn time_stamp_to_duration(time_stamp: f64) -> Duration {
 }
 
 /// [`f64`] `no_std` compatibility wrapper.
+#[cfg(target_arch = "wasm32")]
 #[derive(Clone, Copy)]
 struct F64(f64);
 
+#[cfg(target_arch = "wasm32")]
 impl F64 {
 	/// See [`f64::trunc()`].
 	#[cfg(feature = "std")]
@@ -326,7 +378,7 @@ impl F64 {
 	}
 }
 
-#[cfg(test)]
+#[cfg(all(test, target_arch = "wasm32"))]
 #[cfg_attr(web_time_test_coverage, coverage(off))]
 mod test {
 	//! Testing internal code.
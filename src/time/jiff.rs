@@ -0,0 +1,52 @@
+//! Conversions between `SystemTime` and [`jiff::Timestamp`]. They aim to be
+//! identical to Jiff's implementation for [`std::time::SystemTime`], except
+//! that conversions to `SystemTime` are fallible, because it can't represent
+//! times before [`UNIX_EPOCH`] on the Web.
+//!
+//! This implementation was adapted from Jiff's
+//! [`From` and `TryFrom` implementations](https://github.com/BurntSushi/jiff/blob/jiff-0.2.38/src/timestamp.rs#L2585-L2615).
+
+use jiff::{Error, SignedDuration, Timestamp};
+
+use super::{SystemTime, UNIX_EPOCH};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+impl TryFrom<Timestamp> for SystemTime {
+	type Error = Error;
+
+	/// Fails if `timestamp` can't be represented by [`SystemTime`], e.g. if it
+	/// is before [`UNIX_EPOCH`] on the Web.
+	fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+		let signed = timestamp.as_duration();
+		let duration = signed.unsigned_abs();
+
+		let time = if signed.is_negative() {
+			UNIX_EPOCH.checked_sub(duration)
+		} else {
+			UNIX_EPOCH.checked_add(duration)
+		};
+
+		// CHANGED: Return an error instead of panicking.
+		time.ok_or_else(|| {
+			Error::from_args(format_args!(
+				"timestamp {timestamp} is out of range for `SystemTime`"
+			))
+		})
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+impl TryFrom<SystemTime> for Timestamp {
+	type Error = Error;
+
+	fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+		// CHANGED: Replaced `SignedDuration::system_until()`, which only accepts
+		// `std::time::SystemTime`.
+		let duration = match time.duration_since(UNIX_EPOCH) {
+			Ok(duration) => SignedDuration::try_from(duration)?,
+			Err(error) => -SignedDuration::try_from(error.duration())?,
+		};
+
+		Self::from_duration(duration)
+	}
+}
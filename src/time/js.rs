@@ -1,29 +1,287 @@
 //! Bindings to the JS API.
 
+extern crate alloc;
+
+use alloc::string::String;
+
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+#[cfg(web_time_inline_js)]
+#[wasm_bindgen(inline_js = "
+const performance = globalThis.performance;
+const timeOrigin = performance === undefined ? NaN : performance.timeOrigin;
+
+export function now() {
+	return performance === undefined ? NaN : performance.now();
+}
+
+export function nowWithOrigin() {
+	return performance === undefined ? NaN : timeOrigin + performance.now();
+}
+")]
+extern "C" {
+	/// Binding to [`Performance.now()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now)
+	/// on a [`Performance` object](https://developer.mozilla.org/en-US/docs/Web/API/Performance)
+	/// cached in JS. Returns [`f64::NAN`] if it was not found.
+	#[cfg(not(target_feature = "atomics"))]
+	#[wasm_bindgen(js_name = now)]
+	pub(crate) fn performance_now() -> f64;
+
+	/// Binding to [`Performance.now()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now)
+	/// offset by [`Performance.timeOrigin`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin),
+	/// both cached in JS. Returns [`f64::NAN`] if the
+	/// [`Performance` object](https://developer.mozilla.org/en-US/docs/Web/API/Performance)
+	/// was not found.
+	#[cfg(target_feature = "atomics")]
+	#[wasm_bindgen(js_name = nowWithOrigin)]
+	pub(crate) fn performance_now() -> f64;
+}
 
 #[wasm_bindgen]
 extern "C" {
 	/// Type for the [`Performance` object](https://developer.mozilla.org/en-US/docs/Web/API/Performance).
-	pub(super) type Performance;
+	pub(crate) type Performance;
 
 	/// Holds the [`Performance`](https://developer.mozilla.org/en-US/docs/Web/API/Performance) object.
 	#[wasm_bindgen(thread_local_v2, js_namespace = globalThis, js_name = performance)]
-	pub(super) static PERFORMANCE: Option<Performance>;
+	pub(crate) static PERFORMANCE: Option<Performance>;
 
 	/// Binding to [`Performance.now()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now).
 	#[wasm_bindgen(method)]
-	pub(super) fn now(this: &Performance) -> f64;
+	pub(crate) fn now(this: &Performance) -> f64;
+
+	/// Binding to [`Performance.mark()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/mark).
+	#[cfg(feature = "profiling")]
+	#[wasm_bindgen(method, catch)]
+	pub(crate) fn mark(this: &Performance, name: &str) -> Result<(), JsValue>;
+
+	/// Binding to [`Performance.measure()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/measure).
+	#[cfg(feature = "profiling")]
+	#[wasm_bindgen(method, catch)]
+	pub(crate) fn measure(
+		this: &Performance,
+		name: &str,
+		options: &PerformanceMeasureOptions,
+	) -> Result<(), JsValue>;
+
+	/// Type for the [`PerformanceMeasureOptions` dictionary](https://developer.mozilla.org/en-US/docs/Web/API/Performance/measure#measureoptions).
+	#[cfg(feature = "profiling")]
+	#[wasm_bindgen(js_name = Object)]
+	pub(crate) type PerformanceMeasureOptions;
+
+	/// Creates an empty [`PerformanceMeasureOptions`].
+	#[cfg(feature = "profiling")]
+	#[wasm_bindgen(constructor, js_class = "Object")]
+	pub(crate) fn new() -> PerformanceMeasureOptions;
+
+	/// Sets the `start` field of [`PerformanceMeasureOptions`].
+	#[cfg(feature = "profiling")]
+	#[wasm_bindgen(method, setter = start)]
+	pub(crate) fn set_start(this: &PerformanceMeasureOptions, value: f64);
+
+	/// Sets the `end` field of [`PerformanceMeasureOptions`].
+	#[cfg(feature = "profiling")]
+	#[wasm_bindgen(method, setter = end)]
+	pub(crate) fn set_end(this: &PerformanceMeasureOptions, value: f64);
 
 	/// Holds the [`Performance.timeOrigin`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin).
 	#[cfg(target_feature = "atomics")]
 	#[wasm_bindgen(thread_local_v2, js_namespace = ["globalThis", "performance"], js_name = timeOrigin)]
-	pub(super) static TIME_ORIGIN: f64;
+	pub(crate) static TIME_ORIGIN: f64;
 
 	/// Type for the [`Date` object](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date).
-	pub(super) type Date;
+	pub(crate) type Date;
 
 	/// Binding to [`Date.now()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/now).
 	#[wasm_bindgen(static_method_of = Date)]
-	pub(super) fn now() -> f64;
+	pub(crate) fn now() -> f64;
+
+	/// Creates a new [`Date`] from milliseconds since the Unix epoch with
+	/// [`new Date()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/Date).
+	#[wasm_bindgen(constructor)]
+	pub(crate) fn new(time: f64) -> Date;
+
+	/// Binding to [`Date.prototype.getTimezoneOffset()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getTimezoneOffset).
+	#[wasm_bindgen(method, js_name = getTimezoneOffset)]
+	pub(crate) fn get_timezone_offset(this: &Date) -> f64;
+
+	/// Type for the [`Intl.DateTimeFormat` object](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat).
+	#[wasm_bindgen(js_namespace = Intl)]
+	pub(crate) type DateTimeFormat;
+
+	/// Creates a new [`DateTimeFormat`] with
+	/// [`new Intl.DateTimeFormat()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/DateTimeFormat).
+	#[wasm_bindgen(constructor, js_namespace = Intl, catch)]
+	pub(crate) fn new(
+		locales: Option<&str>,
+		options: &DateTimeFormatOptions,
+	) -> Result<DateTimeFormat, JsValue>;
+
+	/// Binding to [`Intl.DateTimeFormat.prototype.format()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/format).
+	#[wasm_bindgen(method, catch)]
+	pub(crate) fn format(this: &DateTimeFormat, date: &Date) -> Result<String, JsValue>;
+
+	/// Binding to [`Intl.DateTimeFormat.prototype.resolvedOptions()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/resolvedOptions).
+	#[wasm_bindgen(method, js_name = resolvedOptions)]
+	pub(crate) fn resolved_options(this: &DateTimeFormat) -> ResolvedDateTimeFormatOptions;
+
+	/// Type for the object returned by [`DateTimeFormat::resolved_options()`].
+	#[wasm_bindgen(js_name = Object)]
+	pub(crate) type ResolvedDateTimeFormatOptions;
+
+	/// Gets the `timeZone` field of [`ResolvedDateTimeFormatOptions`].
+	#[wasm_bindgen(method, getter = timeZone)]
+	pub(crate) fn time_zone(this: &ResolvedDateTimeFormatOptions) -> Option<String>;
+
+	/// Type for the [`Intl.DateTimeFormat` options](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/DateTimeFormat#options).
+	#[wasm_bindgen(js_name = Object)]
+	pub(crate) type DateTimeFormatOptions;
+
+	/// Creates an empty [`DateTimeFormatOptions`].
+	#[wasm_bindgen(constructor, js_class = "Object")]
+	pub(crate) fn new() -> DateTimeFormatOptions;
+
+	/// Sets the `dateStyle` field of [`DateTimeFormatOptions`].
+	#[wasm_bindgen(method, setter = dateStyle)]
+	pub(crate) fn set_date_style(this: &DateTimeFormatOptions, value: &str);
+
+	/// Sets the `timeStyle` field of [`DateTimeFormatOptions`].
+	#[wasm_bindgen(method, setter = timeStyle)]
+	pub(crate) fn set_time_style(this: &DateTimeFormatOptions, value: &str);
+
+	/// Sets the `timeZone` field of [`DateTimeFormatOptions`].
+	#[wasm_bindgen(method, setter = timeZone)]
+	pub(crate) fn set_time_zone(this: &DateTimeFormatOptions, value: &str);
+
+	/// Sets the `hour12` field of [`DateTimeFormatOptions`].
+	#[wasm_bindgen(method, setter = hour12)]
+	pub(crate) fn set_hour12(this: &DateTimeFormatOptions, value: bool);
+
+	/// Binding to [`setTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout).
+	#[wasm_bindgen(js_name = setTimeout)]
+	pub(crate) fn set_timeout(handler: &Closure<dyn FnMut()>, timeout: i32) -> JsValue;
+
+	/// Binding to [`clearTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/clearTimeout).
+	#[wasm_bindgen(js_name = clearTimeout)]
+	pub(crate) fn clear_timeout(id: &JsValue);
+
+	/// Holds [`requestIdleCallback()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback),
+	/// which is not available in all browsers and workers.
+	#[wasm_bindgen(thread_local_v2, js_namespace = globalThis, js_name = requestIdleCallback)]
+	pub(crate) static REQUEST_IDLE_CALLBACK: JsValue;
+
+	/// Binding to [`requestIdleCallback()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback).
+	#[wasm_bindgen(js_name = requestIdleCallback)]
+	pub(crate) fn request_idle_callback(
+		callback: &Closure<dyn FnMut(IdleDeadline)>,
+		options: &IdleRequestOptions,
+	) -> u32;
+
+	/// Binding to [`cancelIdleCallback()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/cancelIdleCallback).
+	#[wasm_bindgen(js_name = cancelIdleCallback)]
+	pub(crate) fn cancel_idle_callback(handle: u32);
+
+	/// Type for the [`IdleDeadline` object](https://developer.mozilla.org/en-US/docs/Web/API/IdleDeadline).
+	pub(crate) type IdleDeadline;
+
+	/// Binding to [`IdleDeadline.timeRemaining()`](https://developer.mozilla.org/en-US/docs/Web/API/IdleDeadline/timeRemaining).
+	#[wasm_bindgen(method, js_name = timeRemaining)]
+	pub(crate) fn time_remaining(this: &IdleDeadline) -> f64;
+
+	/// Binding to [`IdleDeadline.didTimeout`](https://developer.mozilla.org/en-US/docs/Web/API/IdleDeadline/didTimeout).
+	#[wasm_bindgen(method, getter = didTimeout)]
+	pub(crate) fn did_timeout(this: &IdleDeadline) -> bool;
+
+	/// Type for the [`requestIdleCallback()` options](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback#options).
+	#[wasm_bindgen(js_name = Object)]
+	pub(crate) type IdleRequestOptions;
+
+	/// Creates an empty [`IdleRequestOptions`].
+	#[wasm_bindgen(constructor, js_class = "Object")]
+	pub(crate) fn new() -> IdleRequestOptions;
+
+	/// Sets the `timeout` field of [`IdleRequestOptions`].
+	#[wasm_bindgen(method, setter = timeout)]
+	pub(crate) fn set_timeout(this: &IdleRequestOptions, value: u32);
+
+	/// Binding to [`setTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout)
+	/// with a one-shot callback.
+	#[wasm_bindgen(js_name = setTimeout)]
+	pub(crate) fn set_timeout_once(handler: &JsValue, timeout: i32) -> JsValue;
+
+	/// Type for the [`Scheduler` object](https://developer.mozilla.org/en-US/docs/Web/API/Scheduler).
+	pub(crate) type Scheduler;
+
+	/// Holds the [`Scheduler`](https://developer.mozilla.org/en-US/docs/Web/API/Scheduler)
+	/// object, which is not available in all browsers.
+	#[wasm_bindgen(thread_local_v2, js_namespace = globalThis, js_name = scheduler)]
+	pub(crate) static SCHEDULER: Option<Scheduler>;
+
+	/// Holds [`Scheduler.yield()`](https://developer.mozilla.org/en-US/docs/Web/API/Scheduler/yield),
+	/// which is not available in all browsers.
+	#[wasm_bindgen(method, getter = yield)]
+	pub(crate) fn yield_fn(this: &Scheduler) -> JsValue;
+
+	/// Binding to [`Scheduler.yield()`](https://developer.mozilla.org/en-US/docs/Web/API/Scheduler/yield).
+	#[wasm_bindgen(method, js_name = yield)]
+	pub(crate) fn yield_now(this: &Scheduler) -> Promise;
+
+	/// Type for the [`Promise` object](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise).
+	pub(crate) type Promise;
+
+	/// Binding to [`Promise.prototype.then()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Promise/then).
+	#[wasm_bindgen(method)]
+	pub(crate) fn then(this: &Promise, on_fulfilled: &JsValue) -> Promise;
+
+	/// Holds the [`MessageChannel` constructor](https://developer.mozilla.org/en-US/docs/Web/API/MessageChannel),
+	/// which is not available in all environments.
+	#[wasm_bindgen(thread_local_v2, js_namespace = globalThis, js_name = MessageChannel)]
+	pub(crate) static MESSAGE_CHANNEL: JsValue;
+
+	/// Type for the [`MessageChannel` object](https://developer.mozilla.org/en-US/docs/Web/API/MessageChannel).
+	pub(crate) type MessageChannel;
+
+	/// Creates a new [`MessageChannel`] with
+	/// [`new MessageChannel()`](https://developer.mozilla.org/en-US/docs/Web/API/MessageChannel/MessageChannel).
+	#[wasm_bindgen(constructor)]
+	pub(crate) fn new() -> MessageChannel;
+
+	/// Binding to [`MessageChannel.port1`](https://developer.mozilla.org/en-US/docs/Web/API/MessageChannel/port1).
+	#[wasm_bindgen(method, getter)]
+	pub(crate) fn port1(this: &MessageChannel) -> MessagePort;
+
+	/// Binding to [`MessageChannel.port2`](https://developer.mozilla.org/en-US/docs/Web/API/MessageChannel/port2).
+	#[wasm_bindgen(method, getter)]
+	pub(crate) fn port2(this: &MessageChannel) -> MessagePort;
+
+	/// Type for the [`MessagePort` object](https://developer.mozilla.org/en-US/docs/Web/API/MessagePort).
+	#[derive(Clone)]
+	pub(crate) type MessagePort;
+
+	/// Sets [`MessagePort.onmessage`](https://developer.mozilla.org/en-US/docs/Web/API/MessagePort/message_event).
+	#[wasm_bindgen(method, setter = onmessage)]
+	pub(crate) fn set_onmessage(this: &MessagePort, handler: &JsValue);
+
+	/// Binding to [`MessagePort.postMessage()`](https://developer.mozilla.org/en-US/docs/Web/API/MessagePort/postMessage).
+	#[wasm_bindgen(method, js_name = postMessage)]
+	pub(crate) fn post_message(this: &MessagePort, message: &JsValue);
+
+	/// Binding to [`MessagePort.close()`](https://developer.mozilla.org/en-US/docs/Web/API/MessagePort/close).
+	#[wasm_bindgen(method)]
+	pub(crate) fn close(this: &MessagePort);
+
+	/// Holds [`queueMicrotask()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/queueMicrotask),
+	/// which is not available in all environments.
+	#[wasm_bindgen(thread_local_v2, js_namespace = globalThis, js_name = queueMicrotask)]
+	pub(crate) static QUEUE_MICROTASK: JsValue;
+
+	/// Binding to [`queueMicrotask()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/queueMicrotask).
+	#[wasm_bindgen(js_name = queueMicrotask)]
+	pub(crate) fn queue_microtask(callback: &Closure<dyn FnMut()>);
+
+	/// Binding to [`Math.random()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random).
+	#[wasm_bindgen(js_namespace = Math, js_name = random)]
+	pub(crate) fn math_random() -> f64;
 }
@@ -5,17 +5,30 @@
 	doc = "[`std::time`]: https://doc.rust-lang.org/std/time"
 )]
 
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod alarm;
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(not(target_arch = "wasm32"))]
+mod clock;
 mod instant;
-mod js;
+#[cfg(feature = "jiff")]
+mod jiff;
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod js;
 #[cfg(feature = "serde")]
 mod serde;
 mod system_time;
+#[cfg(feature = "time")]
+mod time_crate;
 
-#[cfg(not(feature = "std"))]
+#[cfg(not(all(target_arch = "wasm32", feature = "std")))]
 pub use core::time::*;
-#[cfg(feature = "std")]
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 pub use std::time::*;
 
+#[cfg(feature = "chrono")]
+pub use self::chrono::OutOfRangeError;
 pub use self::instant::Instant;
 pub use self::system_time::{SystemTime, SystemTimeError};
 
@@ -7,14 +7,17 @@
 	doc = "[`std::time::SystemTime`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html"
 )]
 
-#[cfg(all(all(doc, docsrs), not(feature = "std")))]
+#[cfg(all(all(doc, docsrs), not(all(target_arch = "wasm32", feature = "std"))))]
 use core::error::Error;
 use core::fmt::{self, Display, Formatter};
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 use core::time::Duration;
-#[cfg(feature = "std")]
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 use std::error::Error;
 
+#[cfg(not(target_arch = "wasm32"))]
+use super::clock;
+#[cfg(target_arch = "wasm32")]
 use super::js::Date;
 
 /// See [`std::time::SystemTime`].
@@ -43,6 +46,7 @@ impl SystemTime {
 	)]
 	#[must_use]
 	#[allow(clippy::missing_panics_doc)]
+	#[cfg(target_arch = "wasm32")]
 	pub fn now() -> Self {
 		#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
 		let ms = Date::now() as i64;
@@ -51,6 +55,23 @@ impl SystemTime {
 		Self(Duration::from_millis(ms))
 	}
 
+	/// See [`std::time::SystemTime::now()`].
+	///
+	/// # Panics
+	///
+	/// This call will panic if no system time clock was registered with
+	/// [`register_clock!`](crate::register_clock).
+	#[cfg_attr(
+		not(feature = "std"),
+		doc = "",
+		doc = "[`std::time::SystemTime::now()`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html#method.now"
+	)]
+	#[must_use]
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn now() -> Self {
+		Self(clock::system_time_now())
+	}
+
 	/// See [`std::time::SystemTime::duration_since()`].
 	#[cfg_attr(
 		not(feature = "std"),
@@ -164,6 +185,6 @@ impl Display for SystemTimeError {
 	}
 }
 
-#[cfg(any(feature = "std", all(doc, docsrs)))]
+#[cfg(any(all(target_arch = "wasm32", feature = "std"), all(doc, docsrs)))]
 #[cfg_attr(all(doc, docsrs), doc(cfg(feature = "std")))]
 impl Error for SystemTimeError {}
@@ -0,0 +1,175 @@
+//! Conversions and arithmetic between `SystemTime`, `Instant` and the types of
+//! the [`time`] crate. They aim to be identical to `time`'s implementation for
+//! [`std::time::SystemTime`] and [`std::time::Instant`], except that
+//! conversions to `SystemTime` are fallible, because it can't represent times
+//! before [`UNIX_EPOCH`] on the Web.
+//!
+//! This implementation was adapted from `time`'s
+//! [`OffsetDateTime`](https://github.com/time-rs/time/blob/v0.3.39/time/src/offset_date_time.rs#L1557-L1641),
+//! [`UtcDateTime`](https://github.com/time-rs/time/blob/v0.3.39/time/src/utc_date_time.rs#L1338-L1365)
+//! and
+//! [`Instant`](https://github.com/time-rs/time/blob/v0.3.39/time/src/instant.rs#L195-L256)
+//! implementation.
+
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+use time::error::ConversionRange;
+use time::{Duration as TimeDuration, OffsetDateTime, UtcDateTime};
+
+use super::{Instant, SystemTime, UNIX_EPOCH};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl From<SystemTime> for OffsetDateTime {
+	fn from(time: SystemTime) -> Self {
+		match time.duration_since(UNIX_EPOCH) {
+			Ok(duration) => Self::UNIX_EPOCH + duration,
+			Err(error) => Self::UNIX_EPOCH - error.duration(),
+		}
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<OffsetDateTime> for SystemTime {
+	type Error = ConversionRange;
+
+	/// Fails if `date_time` can't be represented by [`SystemTime`], e.g. if it
+	/// is before [`UNIX_EPOCH`] on the Web.
+	fn try_from(date_time: OffsetDateTime) -> Result<Self, Self::Error> {
+		since_unix_epoch(date_time - OffsetDateTime::UNIX_EPOCH)
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl From<SystemTime> for UtcDateTime {
+	fn from(time: SystemTime) -> Self {
+		match time.duration_since(UNIX_EPOCH) {
+			Ok(duration) => Self::UNIX_EPOCH + duration,
+			Err(error) => Self::UNIX_EPOCH - error.duration(),
+		}
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<UtcDateTime> for SystemTime {
+	type Error = ConversionRange;
+
+	/// Fails if `date_time` can't be represented by [`SystemTime`], e.g. if it
+	/// is before [`UNIX_EPOCH`] on the Web.
+	fn try_from(date_time: UtcDateTime) -> Result<Self, Self::Error> {
+		since_unix_epoch(date_time - UtcDateTime::UNIX_EPOCH)
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl Add<TimeDuration> for SystemTime {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// If the resulting [`SystemTime`] can't be represented.
+	fn add(self, rhs: TimeDuration) -> Self::Output {
+		if rhs.is_negative() {
+			self - rhs.unsigned_abs()
+		} else {
+			self + rhs.unsigned_abs()
+		}
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl AddAssign<TimeDuration> for SystemTime {
+	/// # Panics
+	///
+	/// If the resulting [`SystemTime`] can't be represented.
+	fn add_assign(&mut self, rhs: TimeDuration) {
+		*self = *self + rhs;
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl Sub<TimeDuration> for SystemTime {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// If the resulting [`SystemTime`] can't be represented.
+	fn sub(self, rhs: TimeDuration) -> Self::Output {
+		if rhs.is_negative() {
+			self + rhs.unsigned_abs()
+		} else {
+			self - rhs.unsigned_abs()
+		}
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl SubAssign<TimeDuration> for SystemTime {
+	/// # Panics
+	///
+	/// If the resulting [`SystemTime`] can't be represented.
+	fn sub_assign(&mut self, rhs: TimeDuration) {
+		*self = *self - rhs;
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl Add<TimeDuration> for Instant {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// If the resulting [`Instant`] can't be represented.
+	fn add(self, rhs: TimeDuration) -> Self::Output {
+		if rhs.is_negative() {
+			self - rhs.unsigned_abs()
+		} else {
+			self + rhs.unsigned_abs()
+		}
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl AddAssign<TimeDuration> for Instant {
+	/// # Panics
+	///
+	/// If the resulting [`Instant`] can't be represented.
+	fn add_assign(&mut self, rhs: TimeDuration) {
+		*self = *self + rhs;
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl Sub<TimeDuration> for Instant {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// If the resulting [`Instant`] can't be represented.
+	fn sub(self, rhs: TimeDuration) -> Self::Output {
+		if rhs.is_negative() {
+			self + rhs.unsigned_abs()
+		} else {
+			self - rhs.unsigned_abs()
+		}
+	}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl SubAssign<TimeDuration> for Instant {
+	/// # Panics
+	///
+	/// If the resulting [`Instant`] can't be represented.
+	fn sub_assign(&mut self, rhs: TimeDuration) {
+		*self = *self - rhs;
+	}
+}
+
+/// Returns the [`SystemTime`] `duration` after [`UNIX_EPOCH`].
+fn since_unix_epoch(duration: TimeDuration) -> Result<SystemTime, ConversionRange> {
+	if duration.is_negative() {
+		UNIX_EPOCH.checked_sub(duration.unsigned_abs())
+	} else {
+		UNIX_EPOCH.checked_add(duration.unsigned_abs())
+	}
+	.ok_or(ConversionRange)
+}
@@ -0,0 +1,38 @@
+//! Re-implementation of [`tokio::time::error`].
+//!
+//! [`tokio::time::error`]: https://docs.rs/tokio/1/tokio/time/error/index.html
+
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io;
+
+/// See [`tokio::time::error::Elapsed`].
+///
+/// [`tokio::time::error::Elapsed`]: https://docs.rs/tokio/1/tokio/time/error/struct.Elapsed.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Elapsed(());
+
+impl Elapsed {
+	/// Creates a new [`Elapsed`].
+	pub(crate) const fn new() -> Self {
+		Self(())
+	}
+}
+
+impl Display for Elapsed {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		"deadline has elapsed".fmt(formatter)
+	}
+}
+
+#[cfg(feature = "std")]
+impl Error for Elapsed {}
+
+#[cfg(feature = "std")]
+impl From<Elapsed> for io::Error {
+	fn from(_: Elapsed) -> Self {
+		io::ErrorKind::TimedOut.into()
+	}
+}
@@ -0,0 +1,154 @@
+//! Re-implementation of [`tokio::time::Instant`].
+//!
+//! [`tokio::time::Instant`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html
+
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::Duration;
+
+/// See [`tokio::time::Instant`].
+///
+/// [`tokio::time::Instant`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Instant(crate::Instant);
+
+impl Instant {
+	/// See [`tokio::time::Instant::now()`].
+	///
+	/// [`tokio::time::Instant::now()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.now
+	#[must_use]
+	pub fn now() -> Self {
+		Self(crate::Instant::now())
+	}
+
+	/// See [`tokio::time::Instant::from_std()`].
+	///
+	/// [`tokio::time::Instant::from_std()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.from_std
+	#[must_use]
+	pub const fn from_std(std: crate::Instant) -> Self {
+		Self(std)
+	}
+
+	/// See [`tokio::time::Instant::into_std()`].
+	///
+	/// Returns [`web_time::Instant`](crate::Instant) instead of
+	/// [`std::time::Instant`].
+	///
+	/// [`tokio::time::Instant::into_std()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.into_std
+	/// [`std::time::Instant`]: https://doc.rust-lang.org/std/time/struct.Instant.html
+	#[must_use]
+	pub const fn into_std(self) -> crate::Instant {
+		self.0
+	}
+
+	/// See [`tokio::time::Instant::duration_since()`].
+	///
+	/// [`tokio::time::Instant::duration_since()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.duration_since
+	#[must_use]
+	pub fn duration_since(&self, earlier: Self) -> Duration {
+		self.0.saturating_duration_since(earlier.0)
+	}
+
+	/// See [`tokio::time::Instant::checked_duration_since()`].
+	///
+	/// [`tokio::time::Instant::checked_duration_since()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.checked_duration_since
+	#[must_use]
+	pub fn checked_duration_since(&self, earlier: Self) -> Option<Duration> {
+		self.0.checked_duration_since(earlier.0)
+	}
+
+	/// See [`tokio::time::Instant::saturating_duration_since()`].
+	///
+	/// [`tokio::time::Instant::saturating_duration_since()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.saturating_duration_since
+	#[must_use]
+	pub fn saturating_duration_since(&self, earlier: Self) -> Duration {
+		self.0.saturating_duration_since(earlier.0)
+	}
+
+	/// See [`tokio::time::Instant::elapsed()`].
+	///
+	/// [`tokio::time::Instant::elapsed()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.elapsed
+	#[must_use]
+	pub fn elapsed(&self) -> Duration {
+		self.0.elapsed()
+	}
+
+	/// See [`tokio::time::Instant::checked_add()`].
+	///
+	/// [`tokio::time::Instant::checked_add()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.checked_add
+	#[must_use]
+	pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+		self.0.checked_add(duration).map(Self)
+	}
+
+	/// See [`tokio::time::Instant::checked_sub()`].
+	///
+	/// [`tokio::time::Instant::checked_sub()`]: https://docs.rs/tokio/1/tokio/time/struct.Instant.html#method.checked_sub
+	#[must_use]
+	pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+		self.0.checked_sub(duration).map(Self)
+	}
+
+	/// Roughly 30 years from now, used for timers that should never fire.
+	///
+	/// Same as Tokio's private `Instant::far_future()`.
+	pub(crate) fn far_future() -> Self {
+		Self::now() + Duration::from_secs(86400 * 365 * 30)
+	}
+}
+
+impl From<crate::Instant> for Instant {
+	fn from(time: crate::Instant) -> Self {
+		Self::from_std(time)
+	}
+}
+
+impl From<Instant> for crate::Instant {
+	fn from(value: Instant) -> Self {
+		value.into_std()
+	}
+}
+
+impl Add<Duration> for Instant {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// This function may panic if the resulting point in time cannot be
+	/// represented by the underlying data structure.
+	fn add(self, rhs: Duration) -> Self::Output {
+		Self(self.0 + rhs)
+	}
+}
+
+impl AddAssign<Duration> for Instant {
+	fn add_assign(&mut self, rhs: Duration) {
+		*self = *self + rhs;
+	}
+}
+
+impl Sub for Instant {
+	type Output = Duration;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self.0.saturating_duration_since(rhs.0)
+	}
+}
+
+impl Sub<Duration> for Instant {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// This function may panic if the resulting point in time cannot be
+	/// represented by the underlying data structure.
+	fn sub(self, rhs: Duration) -> Self::Output {
+		Self(self.0 - rhs)
+	}
+}
+
+impl SubAssign<Duration> for Instant {
+	fn sub_assign(&mut self, rhs: Duration) {
+		*self = *self - rhs;
+	}
+}
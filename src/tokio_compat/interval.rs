@@ -0,0 +1,221 @@
+//! Re-implementation of [`tokio::time::Interval`].
+//!
+//! This implementation was adapted from Tokio's
+//! [`Interval`](https://github.com/tokio-rs/tokio/blob/tokio-1.43.0/tokio/src/time/interval.rs)
+//! implementation.
+//!
+//! [`tokio::time::Interval`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use super::{sleep_until, Instant, Sleep};
+use crate::Duration;
+
+/// See [`tokio::time::interval()`].
+///
+/// # Panics
+///
+/// If `period` is zero.
+///
+/// [`tokio::time::interval()`]: https://docs.rs/tokio/1/tokio/time/fn.interval.html
+#[must_use]
+pub fn interval(period: Duration) -> Interval {
+	assert!(period > Duration::ZERO, "`period` must be non-zero.");
+	interval_at(Instant::now(), period)
+}
+
+/// See [`tokio::time::interval_at()`].
+///
+/// # Panics
+///
+/// If `period` is zero.
+///
+/// [`tokio::time::interval_at()`]: https://docs.rs/tokio/1/tokio/time/fn.interval_at.html
+#[must_use]
+pub fn interval_at(start: Instant, period: Duration) -> Interval {
+	assert!(period > Duration::ZERO, "`period` must be non-zero.");
+
+	Interval {
+		// CHANGED: `Sleep` is `Unpin` and doesn't need to be boxed.
+		delay: sleep_until(start),
+		period,
+		missed_tick_behavior: MissedTickBehavior::default(),
+	}
+}
+
+/// See [`tokio::time::MissedTickBehavior`].
+///
+/// [`tokio::time::MissedTickBehavior`]: https://docs.rs/tokio/1/tokio/time/enum.MissedTickBehavior.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MissedTickBehavior {
+	/// See [`tokio::time::MissedTickBehavior::Burst`].
+	///
+	/// [`tokio::time::MissedTickBehavior::Burst`]: https://docs.rs/tokio/1/tokio/time/enum.MissedTickBehavior.html#variant.Burst
+	Burst,
+	/// See [`tokio::time::MissedTickBehavior::Delay`].
+	///
+	/// [`tokio::time::MissedTickBehavior::Delay`]: https://docs.rs/tokio/1/tokio/time/enum.MissedTickBehavior.html#variant.Delay
+	Delay,
+	/// See [`tokio::time::MissedTickBehavior::Skip`].
+	///
+	/// [`tokio::time::MissedTickBehavior::Skip`]: https://docs.rs/tokio/1/tokio/time/enum.MissedTickBehavior.html#variant.Skip
+	Skip,
+}
+
+// CHANGED: `#[default]` on enum variants requires Rust v1.62.
+impl Default for MissedTickBehavior {
+	fn default() -> Self {
+		Self::Burst
+	}
+}
+
+impl MissedTickBehavior {
+	/// If a tick is missed, this method is called to determine when the next
+	/// tick should happen.
+	fn next_timeout(self, timeout: Instant, now: Instant, period: Duration) -> Instant {
+		match self {
+			Self::Burst => timeout + period,
+			Self::Delay => now + period,
+			Self::Skip => {
+				now + period
+					- Duration::from_nanos(
+						u64::try_from((now - timeout).as_nanos() % period.as_nanos())
+							// This operation is practically guaranteed not to fail, as in order
+							// for it to fail, `period` would have to be longer than `now -
+							// timeout`, and both would have to be longer than 584 years.
+							//
+							// If it did fail, there's not a good way to pass the error along to
+							// the user, so we just panic.
+							.expect(
+								"too much time has elapsed since the interval was supposed to tick",
+							),
+					)
+			}
+		}
+	}
+}
+
+/// See [`tokio::time::Interval`].
+///
+/// [`tokio::time::Interval`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html
+#[derive(Debug)]
+pub struct Interval {
+	/// Future that completes the next time the [`Interval`] should tick.
+	delay: Sleep,
+	/// The duration between values yielded by the [`Interval`].
+	period: Duration,
+	/// The strategy [`Interval`] should use when a tick is missed.
+	missed_tick_behavior: MissedTickBehavior,
+}
+
+impl Interval {
+	/// See [`tokio::time::Interval::tick()`].
+	///
+	/// [`tokio::time::Interval::tick()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.tick
+	pub async fn tick(&mut self) -> Instant {
+		// CHANGED: `poll_fn()` requires Rust v1.64.
+		Tick(self).await
+	}
+
+	/// See [`tokio::time::Interval::poll_tick()`].
+	///
+	/// [`tokio::time::Interval::poll_tick()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.poll_tick
+	pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+		// Wait for the delay to be done.
+		// CHANGED: `ready!()` requires Rust v1.64.
+		if Pin::new(&mut self.delay).poll(cx).is_pending() {
+			return Poll::Pending;
+		}
+
+		// Get the time when we were scheduled to tick.
+		let timeout = self.delay.deadline();
+
+		let now = Instant::now();
+
+		// If a tick was not missed, and thus we are being called before the next
+		// tick is due, just schedule the next tick normally, one `period` after
+		// `timeout`.
+		//
+		// However, if a tick took excessively long and we are now behind,
+		// schedule the next tick according to how the user specified with
+		// `MissedTickBehavior`.
+		let next = if now > timeout + Duration::from_millis(5) {
+			self.missed_tick_behavior
+				.next_timeout(timeout, now, self.period)
+		} else {
+			timeout
+				.checked_add(self.period)
+				.unwrap_or_else(Instant::far_future)
+		};
+
+		// CHANGED: `Sleep::reset()` doesn't register the timer either.
+		Pin::new(&mut self.delay).reset(next);
+
+		// Return the time when we were scheduled to tick.
+		Poll::Ready(timeout)
+	}
+
+	/// See [`tokio::time::Interval::reset()`].
+	///
+	/// [`tokio::time::Interval::reset()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.reset
+	pub fn reset(&mut self) {
+		Pin::new(&mut self.delay).reset(Instant::now() + self.period);
+	}
+
+	/// See [`tokio::time::Interval::reset_immediately()`].
+	///
+	/// [`tokio::time::Interval::reset_immediately()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.reset_immediately
+	pub fn reset_immediately(&mut self) {
+		Pin::new(&mut self.delay).reset(Instant::now());
+	}
+
+	/// See [`tokio::time::Interval::reset_after()`].
+	///
+	/// [`tokio::time::Interval::reset_after()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.reset_after
+	pub fn reset_after(&mut self, after: Duration) {
+		Pin::new(&mut self.delay).reset(Instant::now() + after);
+	}
+
+	/// See [`tokio::time::Interval::reset_at()`].
+	///
+	/// [`tokio::time::Interval::reset_at()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.reset_at
+	pub fn reset_at(&mut self, deadline: Instant) {
+		Pin::new(&mut self.delay).reset(deadline);
+	}
+
+	/// See [`tokio::time::Interval::missed_tick_behavior()`].
+	///
+	/// [`tokio::time::Interval::missed_tick_behavior()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.missed_tick_behavior
+	#[must_use]
+	pub const fn missed_tick_behavior(&self) -> MissedTickBehavior {
+		self.missed_tick_behavior
+	}
+
+	/// See [`tokio::time::Interval::set_missed_tick_behavior()`].
+	///
+	/// [`tokio::time::Interval::set_missed_tick_behavior()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.set_missed_tick_behavior
+	pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+		self.missed_tick_behavior = behavior;
+	}
+
+	/// See [`tokio::time::Interval::period()`].
+	///
+	/// [`tokio::time::Interval::period()`]: https://docs.rs/tokio/1/tokio/time/struct.Interval.html#method.period
+	#[must_use]
+	pub const fn period(&self) -> Duration {
+		self.period
+	}
+}
+
+/// Future returned by [`Interval::tick()`].
+struct Tick<'interval>(&'interval mut Interval);
+
+impl Future for Tick<'_> {
+	type Output = Instant;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		self.0.poll_tick(cx)
+	}
+}
@@ -0,0 +1,114 @@
+//! Mirror of the [`tokio::time`] API surface backed by the browsers timers.
+//!
+//! When using the Web implementation, [`setTimeout()`] is used to wake tasks
+//! and [`Instant`] wraps [`web_time::Instant`](crate::Instant). Otherwise this
+//! re-exports [`tokio::time`], so code can switch between both by only
+//! swapping its imports.
+//!
+//! Timers are checked against [`Instant::now()`] when polled, so they never
+//! complete early even though [`setTimeout()`] only has millisecond
+//! precision. Unlike Tokio, no runtime is required: any executor, e.g.
+//! [`wasm-bindgen-futures`], can drive them.
+//!
+//! # Example
+//!
+//! ```
+//! # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+//! #
+//! # #[cfg(target_arch = "wasm32")]
+//! # use tests_web as _;
+//! use web_time::tokio_compat::{self as time, Duration};
+//!
+//! async fn poll_server() -> Result<(), time::error::Elapsed> {
+//! 	let mut interval = time::interval(Duration::from_millis(250));
+//!
+//! 	time::timeout(Duration::from_secs(5), async {
+//! 		loop {
+//! 			interval.tick().await;
+//! 			// Ask the server if it's ready.
+//! 			# break;
+//! 		}
+//! 	})
+//! 	.await
+//! }
+//! #
+//! # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+//! # fn main() {
+//! # 	let _future = poll_server();
+//! # }
+//! ```
+//!
+//! [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+//! [`wasm-bindgen-futures`]: https://crates.io/crates/wasm-bindgen-futures
+#![cfg_attr(
+	not(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	)),
+	doc = "[`tokio::time`]: tokio::time"
+)]
+#![cfg_attr(
+	all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	),
+	doc = "[`tokio::time`]: https://docs.rs/tokio/1/tokio/time/index.html"
+)]
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+pub mod error;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod instant;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod interval;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod sleep;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod timeout;
+
+#[cfg(not(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+)))]
+pub use tokio::time::*;
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+pub use self::instant::Instant;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+pub use self::interval::{interval, interval_at, Interval, MissedTickBehavior};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+pub use self::sleep::{sleep, sleep_until, Sleep};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+pub use self::timeout::{timeout, timeout_at, Timeout};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+pub use crate::Duration;
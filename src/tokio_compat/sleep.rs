@@ -0,0 +1,92 @@
+//! Re-implementation of [`tokio::time::Sleep`].
+//!
+//! [`tokio::time::Sleep`]: https://docs.rs/tokio/1/tokio/time/struct.Sleep.html
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use super::Instant;
+use crate::time::alarm::Alarm;
+use crate::Duration;
+
+/// See [`tokio::time::sleep()`].
+///
+/// [`tokio::time::sleep()`]: https://docs.rs/tokio/1/tokio/time/fn.sleep.html
+pub fn sleep(duration: Duration) -> Sleep {
+	let deadline = Instant::now()
+		.checked_add(duration)
+		.unwrap_or_else(Instant::far_future);
+	sleep_until(deadline)
+}
+
+/// See [`tokio::time::sleep_until()`].
+///
+/// [`tokio::time::sleep_until()`]: https://docs.rs/tokio/1/tokio/time/fn.sleep_until.html
+pub const fn sleep_until(deadline: Instant) -> Sleep {
+	Sleep {
+		deadline,
+		elapsed: false,
+		alarm: Alarm::new(),
+	}
+}
+
+/// See [`tokio::time::Sleep`].
+///
+/// Unlike Tokio's, this implementation is [`Unpin`].
+///
+/// [`tokio::time::Sleep`]: https://docs.rs/tokio/1/tokio/time/struct.Sleep.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Sleep {
+	/// The [`Instant`] at which this future completes.
+	deadline: Instant,
+	/// If the deadline was reached.
+	elapsed: bool,
+	/// Wakes the task once [`Self::deadline`] is reached.
+	alarm: Alarm,
+}
+
+impl Sleep {
+	/// See [`tokio::time::Sleep::deadline()`].
+	///
+	/// [`tokio::time::Sleep::deadline()`]: https://docs.rs/tokio/1/tokio/time/struct.Sleep.html#method.deadline
+	#[must_use]
+	pub const fn deadline(&self) -> Instant {
+		self.deadline
+	}
+
+	/// See [`tokio::time::Sleep::is_elapsed()`].
+	///
+	/// [`tokio::time::Sleep::is_elapsed()`]: https://docs.rs/tokio/1/tokio/time/struct.Sleep.html#method.is_elapsed
+	#[must_use]
+	pub const fn is_elapsed(&self) -> bool {
+		self.elapsed
+	}
+
+	/// See [`tokio::time::Sleep::reset()`].
+	///
+	/// [`tokio::time::Sleep::reset()`]: https://docs.rs/tokio/1/tokio/time/struct.Sleep.html#method.reset
+	pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
+		let this = self.get_mut();
+		this.deadline = deadline;
+		this.elapsed = false;
+		this.alarm.reset(deadline.into_std());
+	}
+}
+
+impl Future for Sleep {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+
+		if this.elapsed {
+			return Poll::Ready(());
+		}
+
+		let poll = this.alarm.poll(this.deadline.into_std(), cx);
+		this.elapsed = poll.is_ready();
+		poll
+	}
+}
@@ -0,0 +1,92 @@
+//! Re-implementation of [`tokio::time::Timeout`].
+//!
+//! [`tokio::time::Timeout`]: https://docs.rs/tokio/1/tokio/time/struct.Timeout.html
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use super::error::Elapsed;
+use super::{sleep, sleep_until, Instant, Sleep};
+use crate::Duration;
+
+/// See [`tokio::time::timeout()`].
+///
+/// [`tokio::time::timeout()`]: https://docs.rs/tokio/1/tokio/time/fn.timeout.html
+pub fn timeout<F: Future>(duration: Duration, future: F) -> Timeout<F> {
+	Timeout {
+		value: future,
+		delay: sleep(duration),
+	}
+}
+
+/// See [`tokio::time::timeout_at()`].
+///
+/// [`tokio::time::timeout_at()`]: https://docs.rs/tokio/1/tokio/time/fn.timeout_at.html
+pub fn timeout_at<F: Future>(deadline: Instant, future: F) -> Timeout<F> {
+	Timeout {
+		value: future,
+		delay: sleep_until(deadline),
+	}
+}
+
+/// See [`tokio::time::Timeout`].
+///
+/// [`tokio::time::Timeout`]: https://docs.rs/tokio/1/tokio/time/struct.Timeout.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Timeout<T> {
+	/// The wrapped future.
+	value: T,
+	/// Completes when the deadline is reached.
+	delay: Sleep,
+}
+
+impl<T> Timeout<T> {
+	/// See [`tokio::time::Timeout::get_ref()`].
+	///
+	/// [`tokio::time::Timeout::get_ref()`]: https://docs.rs/tokio/1/tokio/time/struct.Timeout.html#method.get_ref
+	pub const fn get_ref(&self) -> &T {
+		&self.value
+	}
+
+	/// See [`tokio::time::Timeout::get_mut()`].
+	///
+	/// [`tokio::time::Timeout::get_mut()`]: https://docs.rs/tokio/1/tokio/time/struct.Timeout.html#method.get_mut
+	pub fn get_mut(&mut self) -> &mut T {
+		&mut self.value
+	}
+
+	/// See [`tokio::time::Timeout::into_inner()`].
+	///
+	/// [`tokio::time::Timeout::into_inner()`]: https://docs.rs/tokio/1/tokio/time/struct.Timeout.html#method.into_inner
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<T: Future> Future for Timeout<T> {
+	type Output = Result<T::Output, Elapsed>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		// SAFETY: `value` is never moved out of a pinned `Timeout` and `Timeout`
+		// doesn't implement `Drop`. `delay` is `Unpin`.
+		#[allow(unsafe_code)]
+		let (value, delay) = unsafe {
+			let this = self.get_unchecked_mut();
+			(Pin::new_unchecked(&mut this.value), &mut this.delay)
+		};
+
+		// First, try polling the future.
+		if let Poll::Ready(output) = value.poll(cx) {
+			return Poll::Ready(Ok(output));
+		}
+
+		// Now check the timer.
+		match Pin::new(delay).poll(cx) {
+			Poll::Ready(()) => Poll::Ready(Err(Elapsed::new())),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
@@ -0,0 +1,344 @@
+//! Civil UTC date and time.
+
+use core::fmt::{self, Display, Formatter};
+use core::time::Duration;
+
+use crate::{SystemTime, UNIX_EPOCH};
+
+/// Seconds in a day.
+const SECS_PER_DAY: i128 = 86400;
+
+/// Date and time in UTC according to the proleptic Gregorian calendar, e.g.
+/// for log file rotation or UI labels.
+///
+/// Conversions from [`SystemTime`] always succeed and are exact, leap seconds
+/// are not accounted for, as they are by [`SystemTime`]. Years before `1` are
+/// represented according to ISO 8601, e.g. `0` is 1 BC.
+///
+/// It is [`Display`]ed according to RFC 3339, e.g.
+/// `1994-11-06T08:49:37.5Z`, where fractional seconds are only shown if
+/// present.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::{Duration, SystemTime, UtcDateTime, Weekday};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let time = SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777);
+/// let date_time = UtcDateTime::from(time);
+/// assert_eq!(date_time.year(), 1994);
+/// assert_eq!(date_time.month(), 11);
+/// assert_eq!(date_time.day(), 6);
+/// assert_eq!(date_time.weekday(), Weekday::Sunday);
+/// assert_eq!(date_time.ordinal(), 310);
+///
+/// let date_time = UtcDateTime::new(1994, 11, 6, 8, 49, 37, 0).unwrap();
+/// assert_eq!(date_time.to_system_time(), Some(time));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UtcDateTime {
+	/// Year.
+	year: i64,
+	/// Month, starting with January at `1`.
+	month: u8,
+	/// Day of the month, starting at `1`.
+	day: u8,
+	/// Hour.
+	hour: u8,
+	/// Minute.
+	minute: u8,
+	/// Second.
+	second: u8,
+	/// Nanosecond.
+	nanosecond: u32,
+}
+
+impl UtcDateTime {
+	/// Creates a new [`UtcDateTime`] from its components.
+	///
+	/// Returns [`None`] if any component is out of range.
+	#[must_use]
+	pub const fn new(
+		year: i64,
+		month: u8,
+		day: u8,
+		hour: u8,
+		minute: u8,
+		second: u8,
+		nanosecond: u32,
+	) -> Option<Self> {
+		if month == 0
+			|| month > 12
+			|| day == 0
+			|| day > days_in_month(year, month)
+			|| hour > 23
+			|| minute > 59
+			|| second > 59
+			|| nanosecond > 999_999_999
+		{
+			return None;
+		}
+
+		Some(Self {
+			year,
+			month,
+			day,
+			hour,
+			minute,
+			second,
+			nanosecond,
+		})
+	}
+
+	/// Converts to a [`SystemTime`].
+	///
+	/// Returns [`None`] if the result isn't representable by [`SystemTime`].
+	#[must_use]
+	pub fn to_system_time(self) -> Option<SystemTime> {
+		let secs = self.days_since_epoch() * SECS_PER_DAY
+			+ i128::from(self.hour) * 3600
+			+ i128::from(self.minute) * 60
+			+ i128::from(self.second);
+		let nanos = Duration::from_nanos(self.nanosecond.into());
+
+		if secs >= 0 {
+			UNIX_EPOCH
+				.checked_add(Duration::from_secs(u64::try_from(secs).ok()?))?
+				.checked_add(nanos)
+		} else {
+			UNIX_EPOCH
+				.checked_sub(Duration::from_secs(u64::try_from(-secs).ok()?))?
+				.checked_add(nanos)
+		}
+	}
+
+	/// Returns the year.
+	#[must_use]
+	pub const fn year(self) -> i64 {
+		self.year
+	}
+
+	/// Returns the month, starting with January at `1`.
+	#[must_use]
+	pub const fn month(self) -> u8 {
+		self.month
+	}
+
+	/// Returns the day of the month, starting at `1`.
+	#[must_use]
+	pub const fn day(self) -> u8 {
+		self.day
+	}
+
+	/// Returns the hour.
+	#[must_use]
+	pub const fn hour(self) -> u8 {
+		self.hour
+	}
+
+	/// Returns the minute.
+	#[must_use]
+	pub const fn minute(self) -> u8 {
+		self.minute
+	}
+
+	/// Returns the second.
+	#[must_use]
+	pub const fn second(self) -> u8 {
+		self.second
+	}
+
+	/// Returns the nanosecond.
+	#[must_use]
+	pub const fn nanosecond(self) -> u32 {
+		self.nanosecond
+	}
+
+	/// Returns the day of the week.
+	#[must_use]
+	pub const fn weekday(self) -> Weekday {
+		// 1970-01-01 was a Thursday.
+		match (self.days_since_epoch() + 3).rem_euclid(7) {
+			0 => Weekday::Monday,
+			1 => Weekday::Tuesday,
+			2 => Weekday::Wednesday,
+			3 => Weekday::Thursday,
+			4 => Weekday::Friday,
+			5 => Weekday::Saturday,
+			_ => Weekday::Sunday,
+		}
+	}
+
+	/// Returns the day of the year, starting at `1`.
+	#[must_use]
+	pub const fn ordinal(self) -> u16 {
+		/// Days before each month in a common year.
+		const DAYS_BEFORE: [u16; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+		#[allow(clippy::as_conversions, clippy::indexing_slicing)]
+		let days_before = DAYS_BEFORE[self.month as usize - 1];
+		let leap_day = if self.month > 2 && is_leap_year(self.year) {
+			1
+		} else {
+			0
+		};
+
+		#[allow(clippy::as_conversions)]
+		let day = self.day as u16;
+
+		days_before + leap_day + day
+	}
+
+	/// Returns the number of days since the Unix epoch.
+	///
+	/// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+	#[allow(clippy::as_conversions)]
+	const fn days_since_epoch(self) -> i128 {
+		let month = self.month as i128;
+		let year = if month <= 2 {
+			self.year as i128 - 1
+		} else {
+			self.year as i128
+		};
+		let era = year.div_euclid(400);
+		let year_of_era = year.rem_euclid(400);
+		let month = if month > 2 { month - 3 } else { month + 9 };
+		let day_of_year = (153 * month + 2) / 5 + self.day as i128 - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+		era * 146_097 + day_of_era - 719_468
+	}
+}
+
+impl From<SystemTime> for UtcDateTime {
+	/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+	#[allow(
+		clippy::as_conversions,
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss
+	)]
+	fn from(time: SystemTime) -> Self {
+		let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+			Ok(duration) => (i128::from(duration.as_secs()), duration.subsec_nanos()),
+			Err(error) => {
+				let duration = error.duration();
+				let secs = -i128::from(duration.as_secs());
+
+				if duration.subsec_nanos() == 0 {
+					(secs, 0)
+				} else {
+					(secs - 1, 1_000_000_000 - duration.subsec_nanos())
+				}
+			}
+		};
+
+		// `SystemTime` is limited to 64-bit seconds, so all values fit.
+		let secs_of_day = secs.rem_euclid(SECS_PER_DAY) as u32;
+		let days = secs.div_euclid(SECS_PER_DAY) as i64 + 719_468;
+		let era = days.div_euclid(146_097);
+		let day_of_era = days.rem_euclid(146_097);
+		let year_of_era =
+			(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let month = (5 * day_of_year + 2) / 153;
+		let day = day_of_year - (153 * month + 2) / 5 + 1;
+		let month = if month < 10 { month + 3 } else { month - 9 };
+		let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+		Self {
+			year,
+			month: month as u8,
+			day: day as u8,
+			hour: (secs_of_day / 3600) as u8,
+			minute: (secs_of_day % 3600 / 60) as u8,
+			second: (secs_of_day % 60) as u8,
+			nanosecond: nanos,
+		}
+	}
+}
+
+impl Display for UtcDateTime {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		if self.year < 0 {
+			write!(formatter, "-{:04}", self.year.unsigned_abs())?;
+		} else {
+			write!(formatter, "{:04}", self.year)?;
+		}
+
+		write!(
+			formatter,
+			"-{:02}-{:02}T{:02}:{:02}:{:02}",
+			self.month, self.day, self.hour, self.minute, self.second
+		)?;
+
+		if self.nanosecond != 0 {
+			let mut nanosecond = self.nanosecond;
+			let mut width = 9;
+
+			while nanosecond % 10 == 0 {
+				nanosecond /= 10;
+				width -= 1;
+			}
+
+			write!(formatter, ".{nanosecond:0width$}")?;
+		}
+
+		write!(formatter, "Z")
+	}
+}
+
+/// Day of the week.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Weekday {
+	/// Monday.
+	Monday,
+	/// Tuesday.
+	Tuesday,
+	/// Wednesday.
+	Wednesday,
+	/// Thursday.
+	Thursday,
+	/// Friday.
+	Friday,
+	/// Saturday.
+	Saturday,
+	/// Sunday.
+	Sunday,
+}
+
+impl Weekday {
+	/// Returns the number of this day, starting with Monday at `1`.
+	#[must_use]
+	pub const fn number_from_monday(self) -> u8 {
+		self.number_days_from_monday() + 1
+	}
+
+	/// Returns the number of days since Monday, starting with Monday at `0`.
+	#[must_use]
+	#[allow(clippy::as_conversions)]
+	pub const fn number_days_from_monday(self) -> u8 {
+		self as u8
+	}
+}
+
+/// Returns [`true`] if the given year is a leap year.
+const fn is_leap_year(year: i64) -> bool {
+	year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days in the given month.
+const fn days_in_month(year: i64, month: u8) -> u8 {
+	match month {
+		2 if is_leap_year(year) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
@@ -0,0 +1,129 @@
+//! Cached [`Instant`]s refreshed once per task.
+
+use core::cell::RefCell;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
+
+use crate::clock::Clock;
+use crate::time::js;
+use crate::Instant;
+
+std::thread_local! {
+	/// Cache of this thread.
+	static CACHE: RefCell<Cache> = const { RefCell::new(Cache::new()) };
+}
+
+/// Calls `fun` with the [`Cache`] of this thread.
+fn with<R, F: FnOnce(&mut Cache) -> R>(fun: F) -> R {
+	CACHE.with(|cache| fun(&mut cache.borrow_mut()))
+}
+
+/// Cached [`Instant`] of a thread.
+struct Cache {
+	/// The cached [`Instant`], if sampled in the current task.
+	instant: Option<Instant>,
+	/// How the cache is invalidated.
+	invalidate: Invalidate,
+}
+
+/// How the [`Cache`] is invalidated.
+enum Invalidate {
+	/// Not determined yet.
+	Unknown,
+	/// Callback passed to `queueMicrotask()`.
+	Microtask(Closure<dyn FnMut()>),
+	/// `queueMicrotask()` is not available, so nothing is cached.
+	Unsupported,
+}
+
+impl Cache {
+	/// Creates an empty [`Cache`].
+	const fn new() -> Self {
+		Self {
+			instant: None,
+			invalidate: Invalidate::Unknown,
+		}
+	}
+
+	/// Samples [`Instant::now()`] and queues the invalidation of the cache.
+	fn sample(&mut self) -> Instant {
+		let now = Instant::now();
+
+		if let Invalidate::Unknown = self.invalidate {
+			self.invalidate = if js::QUEUE_MICROTASK.with(JsValue::is_function) {
+				Invalidate::Microtask(Closure::new(|| with(|cache| cache.instant = None)))
+			} else {
+				Invalidate::Unsupported
+			};
+		}
+
+		if let Invalidate::Microtask(closure) = &self.invalidate {
+			if self.instant.is_none() {
+				js::queue_microtask(closure);
+			}
+
+			self.instant = Some(now);
+		}
+
+		now
+	}
+}
+
+/// [`Clock`] returning an [`Instant`] cached for the duration of the current
+/// task, avoiding the cost of calling into JS on every [`Instant::now()`].
+///
+/// The first call samples [`Instant::now()`], later calls return the same
+/// [`Instant`] until all currently queued microtasks have run, which usually
+/// spans the whole current task including all futures polled in it.
+/// This makes it suitable for hot paths that can tolerate the staleness, e.g.
+/// timestamping messages or rate limiting, but not for measuring time within
+/// a single task. [`CoarseClock::refresh()`] samples a new [`Instant`]
+/// explicitly, e.g. during long-running computations.
+///
+/// The cache is invalidated with [`queueMicrotask()`]. Where it is not
+/// available, e.g. in worklets, every call falls back to [`Instant::now()`].
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// use web_time::web::CoarseClock;
+/// use web_time::Clock;
+///
+/// let first = CoarseClock.now();
+/// // Returns the same `Instant` until the current task completes.
+/// assert_eq!(CoarseClock.now(), first);
+///
+/// let refreshed = CoarseClock::refresh();
+/// assert!(refreshed >= first);
+/// assert_eq!(CoarseClock.now(), refreshed);
+/// # }
+/// ```
+///
+/// [`queueMicrotask()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/queueMicrotask
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CoarseClock;
+
+impl CoarseClock {
+	/// Samples [`Instant::now()`] and caches it for the rest of the current
+	/// task.
+	pub fn refresh() -> Instant {
+		with(Cache::sample)
+	}
+}
+
+impl Clock for CoarseClock {
+	fn now(&self) -> Instant {
+		with(|cache| match cache.instant {
+			Some(instant) => instant,
+			None => cache.sample(),
+		})
+	}
+}
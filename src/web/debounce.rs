@@ -0,0 +1,614 @@
+//! Debouncing and throttling of events.
+//!
+//! Stream adapters are woken the same way as [`Delay`](super::Delay),
+//! callbacks schedule their own [`setTimeout()`].
+//!
+//! [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::rc::{Rc, Weak};
+use core::cell::RefCell;
+use core::fmt::{self, Debug, Formatter};
+#[cfg(feature = "futures")]
+use core::pin::Pin;
+#[cfg(feature = "futures")]
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+#[cfg(feature = "futures")]
+use futures_core::{FusedStream, Stream};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
+
+#[cfg(feature = "futures")]
+use crate::time::alarm::Alarm;
+use crate::time::js;
+use crate::Instant;
+
+/// Debounces `stream`, only yielding an item after no other item was received
+/// for `wait`.
+///
+/// By default only the trailing edge, the last item of a burst, is yielded.
+/// See [`Debounce::with_leading()`] and [`Debounce::with_trailing()`].
+///
+/// A pending trailing item is yielded right away when `stream` ends.
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "futures")))]
+pub fn debounce<S: Stream + Unpin>(stream: S, wait: Duration) -> Debounce<S> {
+	Debounce(Adapter::new(stream, Edges::new(Kind::Debounce, wait)))
+}
+
+/// Throttles `stream`, yielding at most one item per `wait`.
+///
+/// By default both edges are yielded: the first item right away and the last
+/// item received during `wait` once it passed. See
+/// [`Throttle::with_leading()`] and [`Throttle::with_trailing()`].
+///
+/// A pending trailing item is yielded right away when `stream` ends.
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "futures")))]
+pub fn throttle<S: Stream + Unpin>(stream: S, wait: Duration) -> Throttle<S> {
+	Throttle(Adapter::new(stream, Edges::new(Kind::Throttle, wait)))
+}
+
+/// [`Stream`] returned by [`debounce()`].
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "futures")))]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Debounce<S: Stream>(Adapter<S>);
+
+/// [`Stream`] returned by [`throttle()`].
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "futures")))]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Throttle<S: Stream>(Adapter<S>);
+
+/// Generates the shared methods and trait implementations of the stream
+/// adapters.
+#[cfg(feature = "futures")]
+macro_rules! adapter {
+	($name:ident) => {
+		impl<S: Stream> $name<S> {
+			/// Sets if the leading edge, the first item of a burst, is yielded
+			/// right away.
+			pub fn with_leading(mut self, enabled: bool) -> Self {
+				self.0.edges.leading = enabled;
+				self
+			}
+
+			/// Sets if the trailing edge, the last item of a burst, is yielded
+			/// once `wait` passed.
+			pub fn with_trailing(mut self, enabled: bool) -> Self {
+				self.0.edges.trailing = enabled;
+				self
+			}
+
+			/// Returns a reference to the underlying [`Stream`].
+			pub const fn get_ref(&self) -> &S {
+				&self.0.stream
+			}
+
+			/// Consumes this adapter, returning the underlying [`Stream`] and
+			/// dropping any pending item.
+			pub fn into_inner(self) -> S {
+				self.0.stream
+			}
+		}
+
+		impl<S: Stream + Unpin> Stream for $name<S> {
+			type Item = S::Item;
+
+			fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+				self.get_mut().0.poll_next(cx)
+			}
+		}
+
+		impl<S: Stream + Unpin> FusedStream for $name<S> {
+			fn is_terminated(&self) -> bool {
+				self.0.done && self.0.edges.pending.is_none()
+			}
+		}
+	};
+}
+
+#[cfg(feature = "futures")]
+adapter!(Debounce);
+#[cfg(feature = "futures")]
+adapter!(Throttle);
+
+/// Implementation of [`Debounce`] and [`Throttle`].
+#[cfg(feature = "futures")]
+struct Adapter<S: Stream> {
+	/// The wrapped [`Stream`].
+	stream: S,
+	/// Decides which items are yielded.
+	edges: Edges<S::Item>,
+	/// Wakes the task when the current window ends.
+	alarm: Alarm,
+	/// If `stream` ended.
+	done: bool,
+}
+
+#[cfg(feature = "futures")]
+impl<S: Stream> Adapter<S> {
+	/// Creates a new [`Adapter`].
+	const fn new(stream: S, edges: Edges<S::Item>) -> Self {
+		Self {
+			stream,
+			edges,
+			alarm: Alarm::new(),
+			done: false,
+		}
+	}
+}
+
+#[cfg(feature = "futures")]
+impl<S: Stream + Unpin> Adapter<S> {
+	/// Polls for the next item to yield.
+	fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+		loop {
+			if self.done {
+				self.alarm.cancel();
+				return Poll::Ready(self.edges.flush());
+			}
+
+			if let Some(item) = self.edges.expire() {
+				return Poll::Ready(Some(item));
+			}
+
+			match Pin::new(&mut self.stream).poll_next(cx) {
+				Poll::Ready(Some(item)) => {
+					if let Some(item) = self.edges.push(item) {
+						return Poll::Ready(Some(item));
+					}
+				}
+				Poll::Ready(None) => self.done = true,
+				Poll::Pending => break,
+			}
+		}
+
+		match self.edges.deadline() {
+			Some(deadline) => {
+				if self.alarm.poll(deadline, cx).is_ready() {
+					// The window ended in the meantime.
+					cx.waker().wake_by_ref();
+				}
+			}
+			None => self.alarm.cancel(),
+		}
+
+		Poll::Pending
+	}
+}
+
+// Pending items are never pinned.
+#[cfg(feature = "futures")]
+impl<S: Stream + Unpin> Unpin for Adapter<S> {}
+
+#[cfg(feature = "futures")]
+impl<S: Stream + Debug> Debug for Adapter<S> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Adapter")
+			.field("stream", &self.stream)
+			.field("done", &self.done)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Debounces calls to a callback, only calling it after it wasn't called for
+/// `wait`.
+///
+/// By default only the trailing edge, the value of the last call of a burst,
+/// is passed on. See [`Debounced::with_leading()`] and
+/// [`Debounced::with_trailing()`]. Cloning a [`Debounced`] shares its state.
+///
+/// Dropping the last clone discards a pending call.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::web::Debounced;
+/// use web_time::Duration;
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let search = Debounced::new(Duration::from_millis(300), |query: &str| {
+/// 	// Send search request.
+/// # 	let _ = query;
+/// });
+///
+/// // Only searches for "web-time".
+/// search.call("web");
+/// search.call("web-");
+/// search.call("web-time");
+/// # }
+/// ```
+pub struct Debounced<T>(Callback<T>);
+
+/// Throttles calls to a callback, calling it at most once per `wait`.
+///
+/// By default both edges are passed on: the value of the first call right
+/// away and the value of the last call during `wait` once it passed. See
+/// [`Throttled::with_leading()`] and [`Throttled::with_trailing()`]. Cloning a
+/// [`Throttled`] shares its state.
+///
+/// Dropping the last clone discards a pending call.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::web::Throttled;
+/// use web_time::Duration;
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let on_scroll = Throttled::new(Duration::from_millis(100), |position: f64| {
+/// 	// Update the UI.
+/// # 	let _ = position;
+/// });
+///
+/// // Updates right away with `0` and with `20` after 100ms.
+/// on_scroll.call(0.);
+/// on_scroll.call(10.);
+/// on_scroll.call(20.);
+/// # }
+/// ```
+pub struct Throttled<T>(Callback<T>);
+
+/// Generates the methods and trait implementations of the callback types.
+macro_rules! callback {
+	($name:ident, $kind:expr) => {
+		impl<T: 'static> $name<T> {
+			/// Creates a new
+			#[doc = concat!("[`", stringify!($name), "`]")]
+			/// passing calls on to `callback`.
+			pub fn new<F: 'static + FnMut(T)>(wait: Duration, callback: F) -> Self {
+				Self(Callback::new(Edges::new($kind, wait), Box::new(callback)))
+			}
+		}
+
+		impl<T> $name<T> {
+			/// Sets if the leading edge, the first call of a burst, is passed
+			/// on right away.
+			#[must_use]
+			pub fn with_leading(self, enabled: bool) -> Self {
+				self.0.shared.state.borrow_mut().edges.leading = enabled;
+				self
+			}
+
+			/// Sets if the trailing edge, the last call of a burst, is passed
+			/// on once `wait` passed.
+			#[must_use]
+			pub fn with_trailing(self, enabled: bool) -> Self {
+				self.0.shared.state.borrow_mut().edges.trailing = enabled;
+				self
+			}
+
+			/// Calls the callback with `value` now or later, depending on the
+			/// previous calls.
+			///
+			/// From within the callback, values passed on right away are
+			/// passed on once it returns.
+			pub fn call(&self, value: T) {
+				self.0.call(value);
+			}
+
+			/// Calls the callback with the pending value, if any, right away
+			/// and ends the current burst.
+			///
+			/// From within the callback, the pending value is passed on once
+			/// it returns.
+			pub fn flush(&self) {
+				self.0.flush();
+			}
+
+			/// Discards the pending value, if any, and ends the current burst.
+			pub fn cancel(&self) {
+				self.0.cancel();
+			}
+
+			/// Returns [`true`] if a call is pending.
+			#[must_use]
+			pub fn is_pending(&self) -> bool {
+				self.0.shared.state.borrow().edges.pending.is_some()
+			}
+		}
+
+		impl<T> Clone for $name<T> {
+			fn clone(&self) -> Self {
+				Self(Callback {
+					shared: Rc::clone(&self.0.shared),
+				})
+			}
+		}
+
+		impl<T: Debug> Debug for $name<T> {
+			fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+				f.debug_tuple(stringify!($name))
+					.field(&self.0.shared.state.borrow().edges)
+					.finish()
+			}
+		}
+	};
+}
+
+callback!(Debounced, Kind::Debounce);
+callback!(Throttled, Kind::Throttle);
+
+/// Implementation of [`Debounced`] and [`Throttled`].
+struct Callback<T> {
+	/// State shared with the `setTimeout()` callback.
+	shared: Rc<Shared<T>>,
+}
+
+/// State of a [`Callback`].
+struct Shared<T> {
+	/// Decides which calls are passed on.
+	state: RefCell<State<T>>,
+	/// The user-supplied callback.
+	callback: RefCell<Box<dyn FnMut(T)>>,
+	/// Values passed on from within the callback, called once it returns.
+	queue: RefCell<VecDeque<T>>,
+	/// Callback passed to `setTimeout()`.
+	closure: Closure<dyn FnMut()>,
+}
+
+/// Mutable part of [`Shared`].
+struct State<T> {
+	/// Decides which calls are passed on.
+	edges: Edges<T>,
+	/// Handle of the currently scheduled `setTimeout()`.
+	handle: Option<JsValue>,
+}
+
+impl<T: 'static> Callback<T> {
+	/// Creates a new [`Callback`].
+	fn new(edges: Edges<T>, callback: Box<dyn FnMut(T)>) -> Self {
+		let shared = Rc::new_cyclic(|weak: &Weak<Shared<T>>| {
+			let weak = Weak::clone(weak);
+
+			Shared {
+				state: RefCell::new(State {
+					edges,
+					handle: None,
+				}),
+				callback: RefCell::new(callback),
+				queue: RefCell::new(VecDeque::new()),
+				closure: Closure::new(move || {
+					if let Some(shared) = weak.upgrade() {
+						shared.fire();
+					}
+				}),
+			}
+		});
+
+		Self { shared }
+	}
+}
+
+impl<T> Callback<T> {
+	/// Passes `value` to [`Edges::push()`].
+	fn call(&self, value: T) {
+		let value = {
+			let mut state = self.shared.state.borrow_mut();
+			let value = state.edges.push(value);
+			self.shared.schedule(&mut state);
+			value
+		};
+
+		self.shared.invoke(value);
+	}
+
+	/// Passes the pending value on right away.
+	fn flush(&self) {
+		let value = {
+			let mut state = self.shared.state.borrow_mut();
+			state.clear();
+			state.edges.flush()
+		};
+
+		self.shared.invoke(value);
+	}
+
+	/// Discards the pending value.
+	fn cancel(&self) {
+		let mut state = self.shared.state.borrow_mut();
+		state.clear();
+		state.edges.flush();
+	}
+}
+
+impl<T> Shared<T> {
+	/// Called by `setTimeout()`.
+	fn fire(&self) {
+		let value = {
+			let mut state = self.state.borrow_mut();
+			state.handle = None;
+			let value = state.edges.expire();
+			self.schedule(&mut state);
+			value
+		};
+
+		self.invoke(value);
+	}
+
+	/// Calls the user-supplied callback with `value`, if any. From within the
+	/// callback, `value` is queued instead and passed on once it returns.
+	fn invoke(&self, value: Option<T>) {
+		let value = match value {
+			Some(value) => value,
+			None => return,
+		};
+
+		if let Ok(mut callback) = self.callback.try_borrow_mut() {
+			callback(value);
+
+			while let Some(value) = self.dequeue() {
+				callback(value);
+			}
+		} else {
+			self.queue.borrow_mut().push_back(value);
+		}
+	}
+
+	/// Takes the next value queued from within the callback.
+	fn dequeue(&self) -> Option<T> {
+		self.queue.borrow_mut().pop_front()
+	}
+
+	/// Schedules a `setTimeout()` for the end of the current window, if none
+	/// is scheduled yet.
+	fn schedule(&self, state: &mut State<T>) {
+		if state.handle.is_some() {
+			// A `setTimeout()` firing before the window ends will simply
+			// schedule again.
+			return;
+		}
+
+		if let Some(deadline) = state.edges.deadline() {
+			let remaining = deadline.saturating_duration_since(Instant::now());
+			// Round up to the next millisecond to not fire too early.
+			let timeout = remaining.as_nanos().saturating_add(999_999) / 1_000_000;
+			let timeout = i32::try_from(timeout).unwrap_or(i32::MAX);
+
+			state.handle = Some(js::set_timeout(&self.closure, timeout));
+		}
+	}
+}
+
+impl<T> State<T> {
+	/// Clears the scheduled `setTimeout()`, if any.
+	fn clear(&mut self) {
+		if let Some(handle) = self.handle.take() {
+			js::clear_timeout(&handle);
+		}
+	}
+}
+
+impl<T> Drop for Shared<T> {
+	fn drop(&mut self) {
+		self.state.get_mut().clear();
+	}
+}
+
+/// Decides which values of a burst are passed on.
+#[derive(Debug)]
+struct Edges<T> {
+	/// If debouncing or throttling.
+	kind: Kind,
+	/// Length of a window.
+	wait: Duration,
+	/// If the first value of a burst is passed on right away.
+	leading: bool,
+	/// If the last value of a burst is passed on at the end of the window.
+	trailing: bool,
+	/// Current window.
+	window: Window,
+	/// Value to pass on at the end of the window.
+	pending: Option<T>,
+}
+
+/// Kind of [`Edges`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+	/// The window restarts with every value.
+	Debounce,
+	/// The window restarts when passing on a trailing value.
+	Throttle,
+}
+
+/// Window in which values are held back.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Window {
+	/// No burst is in progress.
+	Idle,
+	/// A burst is in progress until the given [`Instant`].
+	Until(Instant),
+	/// The end of the burst can't be represented by [`Instant`].
+	Forever,
+}
+
+impl<T> Edges<T> {
+	/// Creates new [`Edges`] with the default edges of `kind`.
+	const fn new(kind: Kind, wait: Duration) -> Self {
+		Self {
+			kind,
+			wait,
+			leading: matches!(kind, Kind::Throttle),
+			trailing: true,
+			window: Window::Idle,
+			pending: None,
+		}
+	}
+
+	/// Opens a new window starting now.
+	fn open(&mut self) {
+		self.window = Instant::now()
+			.checked_add(self.wait)
+			.map_or(Window::Forever, Window::Until);
+	}
+
+	/// Receives a new value. Returns it if it should be passed on right away.
+	fn push(&mut self, value: T) -> Option<T> {
+		let idle = self.window == Window::Idle;
+
+		if idle || self.kind == Kind::Debounce {
+			self.open();
+		}
+
+		if idle && self.leading {
+			Some(value)
+		} else {
+			if self.trailing {
+				self.pending = Some(value);
+			}
+
+			None
+		}
+	}
+
+	/// Returns the pending value if the current window has ended.
+	fn expire(&mut self) -> Option<T> {
+		match self.window {
+			Window::Until(deadline) if deadline <= Instant::now() => (),
+			_ => return None,
+		}
+
+		let value = self.pending.take();
+
+		if self.kind == Kind::Throttle && value.is_some() {
+			self.open();
+		} else {
+			self.window = Window::Idle;
+		}
+
+		value
+	}
+
+	/// Ends the current window and returns the pending value.
+	fn flush(&mut self) -> Option<T> {
+		self.window = Window::Idle;
+		self.pending.take()
+	}
+
+	/// Returns the end of the current window.
+	const fn deadline(&self) -> Option<Instant> {
+		match self.window {
+			Window::Until(deadline) => Some(deadline),
+			Window::Idle | Window::Forever => None,
+		}
+	}
+}
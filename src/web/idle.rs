@@ -0,0 +1,271 @@
+//! Background work with [`requestIdleCallback()`].
+//!
+//! [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
+
+use crate::time::js::{self, IdleDeadline, IdleRequestOptions};
+use crate::Instant;
+
+/// Budget handed out when emulating [`requestIdleCallback()`], which is also
+/// the longest budget browsers hand out.
+///
+/// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+const EMULATED_BUDGET: Duration = Duration::from_millis(50);
+
+/// Waits until the browser is idle with [`requestIdleCallback()`] and returns
+/// the [`IdleBudget`] available for background work.
+///
+/// Where [`requestIdleCallback()`] is not available, e.g. in Safari or in
+/// workers, it is emulated with [`setTimeout()`] handing out a budget of 50ms.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # async fn test() {
+/// use web_time::{web, Duration};
+///
+/// let mut work = 0..10_000;
+///
+/// while !work.is_empty() {
+/// 	let budget = web::idle().await;
+///
+/// 	while budget.remaining() > Duration::ZERO && work.next().is_some() {}
+/// }
+/// # }
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # 	let _future = test();
+/// # }
+/// ```
+///
+/// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+/// [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+pub fn idle() -> Idle {
+	Idle::new(None)
+}
+
+/// Like [`idle()`], but completes after `timeout` even if the browser isn't
+/// idle, which is reported by [`IdleBudget::did_timeout()`].
+///
+/// The timeout is only honored by [`requestIdleCallback()`], the emulation
+/// never times out.
+///
+/// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+pub fn idle_with_timeout(timeout: Duration) -> Idle {
+	Idle::new(Some(timeout))
+}
+
+/// Future returned by [`idle()`] and [`idle_with_timeout()`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Idle {
+	/// Timeout passed to [`requestIdleCallback()`].
+	///
+	/// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+	timeout: Option<Duration>,
+	/// State shared with the callback.
+	shared: Rc<RefCell<Shared>>,
+	/// Registered callback, if polled at least once.
+	callback: Option<Callback>,
+}
+
+/// State shared between [`Idle`] and its callback.
+#[derive(Debug, Default)]
+struct Shared {
+	/// Set once the callback was called.
+	budget: Option<IdleBudget>,
+	/// Woken once the callback was called.
+	waker: Option<Waker>,
+}
+
+/// A registered callback.
+#[derive(Debug)]
+enum Callback {
+	/// Registered with [`requestIdleCallback()`].
+	///
+	/// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+	Idle {
+		/// The JS callback, kept alive until it is called or cancelled.
+		_closure: Closure<dyn FnMut(IdleDeadline)>,
+		/// Handle returned by `requestIdleCallback()`.
+		handle: u32,
+	},
+	/// Registered with [`setTimeout()`].
+	///
+	/// [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+	Timeout {
+		/// The JS callback, kept alive until it is called or cancelled.
+		_closure: Closure<dyn FnMut()>,
+		/// Handle returned by `setTimeout()`.
+		handle: JsValue,
+	},
+}
+
+impl Idle {
+	/// Creates a new [`Idle`] with the given timeout.
+	fn new(timeout: Option<Duration>) -> Self {
+		Self {
+			timeout,
+			shared: Rc::default(),
+			callback: None,
+		}
+	}
+
+	/// Registers the callback.
+	fn register(&self) -> Callback {
+		let shared = Rc::clone(&self.shared);
+
+		if js::REQUEST_IDLE_CALLBACK.with(JsValue::is_function) {
+			let closure = Closure::new(move |deadline: IdleDeadline| {
+				let now = Instant::now();
+				let remaining = deadline.time_remaining().max(0.) / 1000.;
+
+				Shared::complete(
+					&shared,
+					IdleBudget {
+						deadline: now + Duration::from_secs_f64(remaining),
+						did_timeout: deadline.did_timeout(),
+					},
+				);
+			});
+
+			let options = IdleRequestOptions::new();
+
+			if let Some(timeout) = self.timeout {
+				options.set_timeout(u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX));
+			}
+
+			let handle = js::request_idle_callback(&closure, &options);
+
+			Callback::Idle {
+				_closure: closure,
+				handle,
+			}
+		} else {
+			let closure = Closure::new(move || {
+				Shared::complete(
+					&shared,
+					IdleBudget {
+						deadline: Instant::now() + EMULATED_BUDGET,
+						did_timeout: false,
+					},
+				);
+			});
+
+			// Like common polyfills, give other tasks a chance to run first.
+			let handle = js::set_timeout(&closure, 1);
+
+			Callback::Timeout {
+				_closure: closure,
+				handle,
+			}
+		}
+	}
+}
+
+impl Future for Idle {
+	type Output = IdleBudget;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+
+		{
+			let mut shared = this.shared.borrow_mut();
+
+			if let Some(budget) = shared.budget.take() {
+				drop(shared);
+				this.callback = None;
+				return Poll::Ready(budget);
+			}
+
+			match &shared.waker {
+				Some(waker) if waker.will_wake(cx.waker()) => (),
+				_ => shared.waker = Some(cx.waker().clone()),
+			}
+		}
+
+		if this.callback.is_none() {
+			this.callback = Some(this.register());
+		}
+
+		Poll::Pending
+	}
+}
+
+impl Drop for Idle {
+	fn drop(&mut self) {
+		match &self.callback {
+			Some(Callback::Idle { handle, .. }) => js::cancel_idle_callback(*handle),
+			Some(Callback::Timeout { handle, .. }) => js::clear_timeout(handle),
+			None => (),
+		}
+	}
+}
+
+impl Shared {
+	/// Stores the `budget` and wakes the task.
+	fn complete(this: &RefCell<Self>, budget: IdleBudget) {
+		let waker = {
+			let mut shared = this.borrow_mut();
+			shared.budget = Some(budget);
+			shared.waker.take()
+		};
+
+		if let Some(waker) = waker {
+			waker.wake();
+		}
+	}
+}
+
+/// Time available for background work, returned by [`idle()`].
+///
+/// Work should be split into small chunks, checking
+/// [`IdleBudget::remaining()`] in between, to not delay rendering or input
+/// handling.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IdleBudget {
+	/// The [`Instant`] at which the budget is exhausted.
+	deadline: Instant,
+	/// If the callback was called because the timeout elapsed.
+	did_timeout: bool,
+}
+
+impl IdleBudget {
+	/// Returns the [`Instant`] at which this budget is exhausted.
+	#[must_use]
+	pub const fn deadline(&self) -> Instant {
+		self.deadline
+	}
+
+	/// Returns the time remaining in this budget, which is zero if it is
+	/// exhausted.
+	#[must_use]
+	pub fn remaining(&self) -> Duration {
+		self.deadline.saturating_duration_since(Instant::now())
+	}
+
+	/// Returns [`true`] if the browser wasn't idle and the timeout passed to
+	/// [`idle_with_timeout()`] elapsed instead, in which case the budget is
+	/// usually exhausted already.
+	#[must_use]
+	pub const fn did_timeout(&self) -> bool {
+		self.did_timeout
+	}
+}
@@ -0,0 +1,347 @@
+//! Local time zone information and formatting through the [`Date`] and
+//! [`Intl.DateTimeFormat`] APIs.
+//!
+//! [`Date`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date
+//! [`Intl.DateTimeFormat`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat
+
+extern crate alloc;
+
+use alloc::string::String;
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "jiff"
+))]
+use jiff::tz::Offset;
+#[cfg(feature = "jiff")]
+use jiff::tz::TimeZone;
+#[cfg(feature = "jiff")]
+use jiff::{Timestamp, Zoned};
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+use crate::time::js::{Date, DateTimeFormat, DateTimeFormatOptions};
+use crate::SystemTime;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+use crate::UNIX_EPOCH;
+
+/// Returns the offset of the local time zone from UTC at the given time in
+/// seconds, positive east of UTC, with [`Date.prototype.getTimezoneOffset()`].
+///
+/// Returns [`None`] if the time can't be represented by [`Date`] or when not
+/// using the Web implementation. Use a dedicated time zone library on other
+/// platforms instead.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::{web, SystemTime};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// if let Some(offset) = web::local_offset_at(SystemTime::now()) {
+/// 	assert!(offset.unsigned_abs() <= 24 * 3600);
+/// }
+/// # }
+/// ```
+///
+/// [`Date`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date
+/// [`Date.prototype.getTimezoneOffset()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getTimezoneOffset
+#[must_use]
+#[allow(clippy::missing_const_for_fn, unused_variables)]
+pub fn local_offset_at(time: SystemTime) -> Option<i32> {
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	))]
+	{
+		// `getTimezoneOffset()` returns minutes, positive west of UTC.
+		let offset = to_date(time).get_timezone_offset() * -60.;
+
+		if offset.is_finite() {
+			#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+			return Some(offset as i32);
+		}
+	}
+
+	None
+}
+
+/// Returns the [IANA time zone name] of the local time zone, e.g.
+/// `Europe/Berlin`, with [`Intl.DateTimeFormat.prototype.resolvedOptions()`].
+///
+/// Returns [`None`] if the browser doesn't report a time zone or when not using
+/// the Web implementation.
+///
+/// [IANA time zone name]: https://www.iana.org/time-zones
+/// [`Intl.DateTimeFormat.prototype.resolvedOptions()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/resolvedOptions
+#[must_use]
+#[allow(clippy::missing_const_for_fn)]
+pub fn local_time_zone_name() -> Option<String> {
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	))]
+	{
+		let format = DateTimeFormat::new(None, &DateTimeFormatOptions::new()).ok()?;
+		format
+			.resolved_options()
+			.time_zone()
+			.filter(|name| !name.is_empty())
+	}
+
+	#[cfg(not(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	)))]
+	None
+}
+
+/// Formats the given time for humans according to the users locale and time
+/// zone with [`Intl.DateTimeFormat`].
+///
+/// Returns [`None`] if the time can't be represented by [`Date`], if the
+/// [`FormatOptions`] are rejected by the browser, e.g. an unknown time zone, or
+/// when not using the Web implementation.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::web::{self, FormatOptions, Style};
+/// use web_time::{Duration, SystemTime};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// let time = SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777);
+/// let options = FormatOptions::new()
+/// 	.locale("en-US")
+/// 	.time_zone("UTC")
+/// 	.date_style(Style::Long);
+///
+/// if let Some(string) = web::format_local(time, &options) {
+/// 	assert_eq!(string, "November 6, 1994");
+/// }
+/// # }
+/// ```
+///
+/// [`Date`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date
+/// [`Intl.DateTimeFormat`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat
+#[must_use]
+#[allow(clippy::missing_const_for_fn, unused_variables)]
+pub fn format_local(time: SystemTime, options: &FormatOptions<'_>) -> Option<String> {
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	))]
+	{
+		let js_options = DateTimeFormatOptions::new();
+
+		if let Some(style) = options.date_style {
+			js_options.set_date_style(style.as_str());
+		}
+
+		if let Some(style) = options.time_style {
+			js_options.set_time_style(style.as_str());
+		}
+
+		if let Some(time_zone) = options.time_zone {
+			js_options.set_time_zone(time_zone);
+		}
+
+		if let Some(hour12) = options.hour12 {
+			js_options.set_hour12(hour12);
+		}
+
+		DateTimeFormat::new(options.locale, &js_options)
+			.ok()?
+			.format(&to_date(time))
+			.ok()
+	}
+
+	#[cfg(not(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	)))]
+	None
+}
+
+/// Returns the current time as a [`Zoned`] in the local time zone.
+///
+/// On Web the time zone reported by [`local_time_zone_name()`] is looked up in
+/// Jiff's time zone database, which requires enabling one of Jiff's `tzdb-*`
+/// crate features, e.g. `tzdb-bundle-always`. If that fails, a fixed offset
+/// from [`local_offset_at()`] is used instead, falling back to UTC. On other
+/// platforms this uses [`TimeZone::system()`], like [`Zoned::now()`].
+///
+/// # Panics
+///
+/// If [`SystemTime::now()`] is out of range for [`Timestamp`].
+#[cfg(feature = "jiff")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "jiff")))]
+#[must_use]
+pub fn zoned_now() -> Zoned {
+	let now = SystemTime::now();
+	let timestamp = Timestamp::try_from(now).expect("system time is valid");
+
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	))]
+	let time_zone = local_time_zone_name()
+		.and_then(|name| TimeZone::get(&name).ok())
+		.or_else(|| {
+			local_offset_at(now)
+				.and_then(|offset| Offset::from_seconds(offset).ok())
+				.map(TimeZone::fixed)
+		})
+		.unwrap_or(TimeZone::UTC);
+	#[cfg(not(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	)))]
+	let time_zone = TimeZone::system();
+
+	timestamp.to_zoned(time_zone)
+}
+
+/// Converts a [`SystemTime`] to a JS [`Date`].
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+fn to_date(time: SystemTime) -> Date {
+	let duration = time
+		.duration_since(UNIX_EPOCH)
+		.expect("found time before the Unix epoch");
+
+	#[allow(
+		clippy::as_conversions,
+		clippy::cast_precision_loss,
+		clippy::suboptimal_flops
+	)]
+	let ms = duration.as_secs() as f64 * 1000. + f64::from(duration.subsec_nanos()) / 1_000_000.;
+
+	Date::new(ms)
+}
+
+/// Options for [`format_local()`].
+///
+/// Fields that are not set are left to the defaults of
+/// [`Intl.DateTimeFormat`], which only formats the date if neither
+/// [`date_style()`](Self::date_style) nor [`time_style()`](Self::time_style)
+/// are set.
+///
+/// [`Intl.DateTimeFormat`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/DateTimeFormat#options
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FormatOptions<'value> {
+	/// BCP 47 language tag.
+	locale: Option<&'value str>,
+	/// IANA time zone name.
+	time_zone: Option<&'value str>,
+	/// Formatting style of the date.
+	date_style: Option<Style>,
+	/// Formatting style of the time.
+	time_style: Option<Style>,
+	/// If a 12-hour clock should be used.
+	hour12: Option<bool>,
+}
+
+impl<'value> FormatOptions<'value> {
+	/// Creates new [`FormatOptions`] using the users locale and time zone.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			locale: None,
+			time_zone: None,
+			date_style: None,
+			time_style: None,
+			hour12: None,
+		}
+	}
+
+	/// Sets the [BCP 47 language tag] to use instead of the users locale, e.g.
+	/// `en-US`.
+	///
+	/// [BCP 47 language tag]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl#locales_argument
+	#[must_use]
+	pub const fn locale(mut self, locale: &'value str) -> Self {
+		self.locale = Some(locale);
+		self
+	}
+
+	/// Sets the [IANA time zone name] to use instead of the users time zone,
+	/// e.g. `UTC` or `Europe/Berlin`.
+	///
+	/// [IANA time zone name]: https://www.iana.org/time-zones
+	#[must_use]
+	pub const fn time_zone(mut self, time_zone: &'value str) -> Self {
+		self.time_zone = Some(time_zone);
+		self
+	}
+
+	/// Sets the formatting [`Style`] of the date.
+	#[must_use]
+	pub const fn date_style(mut self, style: Style) -> Self {
+		self.date_style = Some(style);
+		self
+	}
+
+	/// Sets the formatting [`Style`] of the time.
+	#[must_use]
+	pub const fn time_style(mut self, style: Style) -> Self {
+		self.time_style = Some(style);
+		self
+	}
+
+	/// Sets if a 12-hour clock should be used instead of the locales default.
+	#[must_use]
+	pub const fn hour12(mut self, hour12: bool) -> Self {
+		self.hour12 = Some(hour12);
+		self
+	}
+}
+
+/// Formatting style of the date or time for [`FormatOptions`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Style {
+	/// E.g. `Sunday, November 6, 1994`.
+	Full,
+	/// E.g. `November 6, 1994`.
+	Long,
+	/// E.g. `Nov 6, 1994`.
+	Medium,
+	/// E.g. `11/6/94`.
+	Short,
+}
+
+impl Style {
+	/// Returns the name used by [`Intl.DateTimeFormat`].
+	///
+	/// [`Intl.DateTimeFormat`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/DateTimeFormat#datestyle
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web)
+	))]
+	const fn as_str(self) -> &'static str {
+		match self {
+			Self::Full => "full",
+			Self::Long => "long",
+			Self::Medium => "medium",
+			Self::Short => "short",
+		}
+	}
+}
@@ -0,0 +1,132 @@
+//! Platform-specific extensions to [`web-time`](crate) for the Web platform.
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "std"
+))]
+mod coarse_clock;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod debounce;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod idle;
+#[cfg(any(target_arch = "wasm32", not(target_os = "none")))]
+mod local;
+pub mod profiling;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod retry;
+mod synced_clock;
+#[cfg(any(
+	all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web),
+		feature = "std"
+	),
+	all(doc, docsrs)
+))]
+mod system_time_ext;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod time_slicer;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod timer;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod timer_wheel;
+#[cfg(feature = "tracing")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "tracing")))]
+pub mod tracing;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+mod yield_now;
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "std"
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(all(Web, feature = "std"))))]
+pub use self::coarse_clock::CoarseClock;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "futures"
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(all(Web, feature = "futures"))))]
+pub use self::debounce::{debounce, throttle, Debounce, Throttle};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+pub use self::debounce::{Debounced, Throttled};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+pub use self::idle::{idle, idle_with_timeout, Idle, IdleBudget};
+#[cfg(all(any(target_arch = "wasm32", not(target_os = "none")), feature = "jiff"))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "jiff")))]
+pub use self::local::zoned_now;
+#[cfg(any(target_arch = "wasm32", not(target_os = "none")))]
+pub use self::local::{format_local, local_offset_at, local_time_zone_name, FormatOptions, Style};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+pub use self::retry::retry;
+pub use self::synced_clock::{SampleError, SyncedClock};
+#[cfg(any(
+	all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web),
+		feature = "std"
+	),
+	all(doc, docsrs)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(all(Web, feature = "std"))))]
+pub use self::system_time_ext::SystemTimeExt;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+pub use self::time_slicer::TimeSlicer;
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+pub use self::timer::{Delay, Timer};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+pub use self::timer_wheel::{TimerKey, TimerWheel};
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web)
+))]
+#[cfg_attr(all(doc, docsrs), doc(cfg(Web)))]
+pub use self::yield_now::{yield_now, YieldNow};
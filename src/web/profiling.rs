@@ -0,0 +1,133 @@
+//! Integration with the [User Timing API] to show marks and measurements in
+//! the browsers performance tools.
+//!
+//! All functions compile to no-ops unless the `profiling` crate feature is
+//! enabled and the Web implementation is used. Errors thrown by the browser,
+//! e.g. when using a reserved name, are ignored.
+//!
+//! # Example
+//!
+//! ```
+//! # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+//! #
+//! # #[cfg(target_arch = "wasm32")]
+//! # use tests_web as _;
+//! use web_time::web::profiling::{self, Span};
+//! use web_time::Instant;
+//!
+//! # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+//! # fn main() {
+//! profiling::mark("start");
+//!
+//! let start = Instant::now();
+//! // Do some work.
+//! profiling::measure("work", start, Instant::now());
+//!
+//! {
+//! 	let _span = Span::new("more work");
+//! 	// Do some more work, measured when `_span` is dropped.
+//! }
+//! # }
+//! ```
+//!
+//! [User Timing API]: https://developer.mozilla.org/en-US/docs/Web/API/Performance_API/User_timing
+
+#[cfg(all(
+	target_arch = "wasm32",
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
+	feature = "profiling"
+))]
+use crate::time::js::{PerformanceMeasureOptions, PERFORMANCE};
+use crate::Instant;
+
+/// Creates a named [`PerformanceMark`] at the current time with
+/// [`Performance.mark()`].
+///
+/// [`PerformanceMark`]: https://developer.mozilla.org/en-US/docs/Web/API/PerformanceMark
+/// [`Performance.mark()`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/mark
+#[allow(clippy::missing_const_for_fn, unused_variables)]
+pub fn mark(name: &str) {
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web),
+		feature = "profiling"
+	))]
+	PERFORMANCE.with(|performance| {
+		if let Some(performance) = performance {
+			let _ = performance.mark(name);
+		}
+	});
+}
+
+/// Creates a named [`PerformanceMeasure`] between `start` and `end` with
+/// [`Performance.measure()`].
+///
+/// [`PerformanceMeasure`]: https://developer.mozilla.org/en-US/docs/Web/API/PerformanceMeasure
+/// [`Performance.measure()`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/measure
+#[allow(clippy::missing_const_for_fn, unused_variables)]
+pub fn measure(name: &str, start: Instant, end: Instant) {
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web),
+		feature = "profiling"
+	))]
+	PERFORMANCE.with(|performance| {
+		if let Some(performance) = performance {
+			let options = PerformanceMeasureOptions::new();
+			options.set_start(start.to_time_stamp());
+			options.set_end(end.to_time_stamp());
+			let _ = performance.measure(name, &options);
+		}
+	});
+}
+
+/// Guard that [`measure()`]s the time from its creation until it is dropped.
+#[derive(Debug)]
+#[must_use = "the measurement ends when the `Span` is dropped"]
+pub struct Span<'name> {
+	/// Name of the measurement.
+	#[cfg_attr(
+		not(all(
+			target_arch = "wasm32",
+			any(target_os = "unknown", target_os = "none", web_time_force_web),
+			feature = "profiling"
+		)),
+		allow(dead_code)
+	)]
+	name: &'name str,
+	/// Time this [`Span`] was created.
+	#[cfg(all(
+		target_arch = "wasm32",
+		any(target_os = "unknown", target_os = "none", web_time_force_web),
+		feature = "profiling"
+	))]
+	start: Instant,
+}
+
+impl<'name> Span<'name> {
+	/// Starts a new [`Span`] with the given name.
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn new(name: &'name str) -> Self {
+		Self {
+			name,
+			#[cfg(all(
+				target_arch = "wasm32",
+				any(target_os = "unknown", target_os = "none", web_time_force_web),
+				feature = "profiling"
+			))]
+			start: Instant::now(),
+		}
+	}
+}
+
+#[allow(clippy::empty_drop)]
+impl Drop for Span<'_> {
+	fn drop(&mut self) {
+		#[cfg(all(
+			target_arch = "wasm32",
+			any(target_os = "unknown", target_os = "none", web_time_force_web),
+			feature = "profiling"
+		))]
+		measure(self.name, self.start, Instant::now());
+	}
+}
@@ -0,0 +1,65 @@
+//! Retrying fallible operations.
+
+use core::future::Future;
+use core::time::Duration;
+
+use super::Delay;
+use crate::Instant;
+
+/// Calls `op` until it succeeds, waiting for the delays of `backoff` in
+/// between with [`Delay`].
+///
+/// # Errors
+///
+/// Returns the last error of `op` once `backoff` is exhausted, e.g. because
+/// the deadline of a [`Backoff`](crate::Backoff) would be exceeded, or if a
+/// delay can't be represented by [`Instant`].
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # async fn fetch() -> Result<(), ()> { Ok(()) }
+/// #
+/// # async fn test() {
+/// use web_time::{web, Backoff, Duration, Instant};
+///
+/// let backoff = Backoff::decorrelated_jitter(Duration::from_millis(100))
+/// 	.with_max_delay(Duration::from_secs(5))
+/// 	.with_deadline(Instant::now() + Duration::from_secs(30));
+/// let result = web::retry(fetch, backoff).await;
+/// # let _ = result;
+/// # }
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # 	let _future = test();
+/// # }
+/// ```
+pub async fn retry<T, E, F, Fut, B>(mut op: F, backoff: B) -> Result<T, E>
+where
+	F: FnMut() -> Fut,
+	Fut: Future<Output = Result<T, E>>,
+	B: IntoIterator<Item = Duration>,
+{
+	let mut backoff = backoff.into_iter();
+
+	loop {
+		let error = match op().await {
+			Ok(value) => return Ok(value),
+			Err(error) => error,
+		};
+
+		match backoff
+			.next()
+			.and_then(|delay| Instant::now().checked_add(delay))
+		{
+			Some(deadline) => Delay::at(deadline).await,
+			None => return Err(error),
+		}
+	}
+}
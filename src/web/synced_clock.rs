@@ -0,0 +1,332 @@
+//! Synchronization with a server clock.
+
+use core::fmt::{self, Display, Formatter};
+use core::time::Duration;
+#[cfg(any(
+	all(target_arch = "wasm32", feature = "std"),
+	all(not(target_arch = "wasm32"), not(target_os = "none"))
+))]
+use std::error::Error;
+
+use crate::clock::{Clock, DefaultClock};
+use crate::httpdate::HttpDateExt;
+use crate::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Number of samples retained by [`SyncedClock`].
+const SAMPLES: usize = 8;
+
+/// Nanoseconds in a second.
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Estimates the time of a server from round-trip samples, similar to
+/// [Cristian's algorithm] or NTP.
+///
+/// Client clocks can't be trusted, e.g. to check for token expiry, as they are
+/// often minutes off. Each sample consists of the [`Instant`] a request was
+/// sent, the time reported by the server and the [`Instant`] the response was
+/// received. The server time is assumed to have been taken in the middle of
+/// the round-trip, making the uncertainty of each sample half its round-trip
+/// time.
+///
+/// The last 8 samples are retained. Samples that don't agree with the
+/// majority of other samples are rejected as outliers with [Marzullo's
+/// algorithm]. The corrected time is then anchored to [`Instant`], making it
+/// independent from changes to the client's [`SystemTime`].
+///
+/// The offset to the client's clock can be computed with e.g.
+/// `clock.now()?.duration_since(SystemTime::now())`.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// use web_time::web::SyncedClock;
+/// use web_time::{Duration, Instant, SystemTime};
+///
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # let fetch_server_time = || SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+/// let mut clock = SyncedClock::new();
+///
+/// let send = Instant::now();
+/// let server_time = fetch_server_time();
+/// clock.add_sample(send, server_time, Instant::now()).unwrap();
+///
+/// let now = clock.now().unwrap();
+/// let uncertainty = clock.uncertainty().unwrap();
+/// # let _ = (now, uncertainty);
+/// # }
+/// ```
+///
+/// [Cristian's algorithm]: https://en.wikipedia.org/wiki/Cristian%27s_algorithm
+/// [Marzullo's algorithm]: https://en.wikipedia.org/wiki/Marzullo%27s_algorithm
+#[derive(Clone, Debug)]
+pub struct SyncedClock<C = DefaultClock> {
+	/// Time source.
+	clock: C,
+	/// Point in time all samples are relative to.
+	origin: Instant,
+	/// Retained samples, newest first.
+	samples: [Option<Sample>; SAMPLES],
+	/// Current estimate and the number of samples agreeing with it.
+	estimate: Option<(Sample, usize)>,
+}
+
+/// Estimate of the server time at [`SyncedClock::origin`].
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+	/// Nanoseconds since [`UNIX_EPOCH`].
+	center: i128,
+	/// Uncertainty in nanoseconds.
+	radius: i128,
+}
+
+impl Sample {
+	/// Lower bound.
+	const fn start(self) -> i128 {
+		self.center - self.radius
+	}
+
+	/// Upper bound.
+	const fn end(self) -> i128 {
+		self.center + self.radius
+	}
+}
+
+impl SyncedClock {
+	/// Creates a new [`SyncedClock`] without any samples.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::with_clock(DefaultClock)
+	}
+}
+
+impl Default for SyncedClock {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<C: Clock> SyncedClock<C> {
+	/// Creates a new [`SyncedClock`] without any samples, using the given
+	/// [`Clock`].
+	#[must_use]
+	pub fn with_clock(clock: C) -> Self {
+		let origin = clock.now();
+
+		Self {
+			clock,
+			origin,
+			samples: [None; SAMPLES],
+			estimate: None,
+		}
+	}
+
+	/// Adds a round-trip sample.
+	///
+	/// # Errors
+	///
+	/// If `recv` is earlier than `send`.
+	pub fn add_sample(
+		&mut self,
+		send: Instant,
+		server_time: SystemTime,
+		recv: Instant,
+	) -> Result<(), SampleError> {
+		self.add(send, system_time_to_nanos(server_time), recv, 0)
+	}
+
+	/// Adds a round-trip sample from the value of a HTTP [`Date`] header, e.g.
+	/// `Sun, 06 Nov 1994 08:49:37 GMT`. See [`httpdate`](crate::httpdate) for
+	/// supported formats.
+	///
+	/// As the [`Date`] header only has a precision of one second, these
+	/// samples have an additional uncertainty of half a second.
+	///
+	/// # Errors
+	///
+	/// If `recv` is earlier than `send` or `date` isn't a valid HTTP date.
+	///
+	/// [`Date`]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Date
+	pub fn add_date_header(
+		&mut self,
+		send: Instant,
+		date: &str,
+		recv: Instant,
+	) -> Result<(), SampleError> {
+		let server_time = SystemTime::from_http_date(date).map_err(|_| SampleError::InvalidDate)?;
+		let half = NANOS_PER_SEC / 2;
+		self.add(send, system_time_to_nanos(server_time) + half, recv, half)
+	}
+
+	/// Removes all samples.
+	pub fn reset(&mut self) {
+		self.samples = [None; SAMPLES];
+		self.estimate = None;
+	}
+
+	/// Returns the number of samples used for the current estimate, not
+	/// counting rejected outliers.
+	#[must_use]
+	pub fn samples(&self) -> usize {
+		self.estimate.map_or(0, |(_, count)| count)
+	}
+
+	/// Returns the corrected [`SystemTime`] at the current [`Instant`].
+	///
+	/// Returns [`None`] if no samples were added yet.
+	#[must_use]
+	pub fn now(&self) -> Option<SystemTime> {
+		self.system_time_at(self.clock.now())
+	}
+
+	/// Returns the corrected [`SystemTime`] at the given [`Instant`].
+	///
+	/// Returns [`None`] if no samples were added yet or the result isn't
+	/// representable.
+	#[must_use]
+	pub fn system_time_at(&self, instant: Instant) -> Option<SystemTime> {
+		let (estimate, _) = self.estimate?;
+		nanos_to_system_time(estimate.center + self.relative(instant))
+	}
+
+	/// Returns the uncertainty of the current estimate, the corrected time is
+	/// expected to be within this distance of the server time.
+	///
+	/// Returns [`None`] if no samples were added yet.
+	#[must_use]
+	pub fn uncertainty(&self) -> Option<Duration> {
+		self.estimate
+			.and_then(|(estimate, _)| nanos_to_duration(estimate.radius))
+	}
+
+	/// Adds a sample and updates the estimate.
+	fn add(
+		&mut self,
+		send: Instant,
+		server_time: i128,
+		recv: Instant,
+		precision: i128,
+	) -> Result<(), SampleError> {
+		let round_trip = recv
+			.checked_duration_since(send)
+			.ok_or(SampleError::NegativeRoundTrip)?;
+		let middle = (self.relative(send) + self.relative(recv)) / 2;
+		// Round up to not underestimate the uncertainty.
+		let radius = (duration_to_nanos(round_trip) + 1) / 2 + precision;
+
+		// Make room for the new sample by dropping the oldest one.
+		self.samples.rotate_right(1);
+		let [newest, ..] = &mut self.samples;
+		*newest = Some(Sample {
+			center: server_time - middle,
+			radius,
+		});
+		self.estimate = self.estimate();
+
+		Ok(())
+	}
+
+	/// Marzullo's algorithm: find the smallest interval agreed upon by the
+	/// most samples.
+	fn estimate(&self) -> Option<(Sample, usize)> {
+		let mut best: Option<(usize, i128, i128)> = None;
+
+		for candidate in self.samples.iter().flatten() {
+			let start = candidate.start();
+			let mut end = candidate.end();
+			let mut count = 0;
+
+			for sample in self.samples.iter().flatten() {
+				if sample.start() <= start && start <= sample.end() {
+					end = end.min(sample.end());
+					count += 1;
+				}
+			}
+
+			if best.map_or(true, |(best_count, best_start, best_end)| {
+				count > best_count || (count == best_count && end - start < best_end - best_start)
+			}) {
+				best = Some((count, start, end));
+			}
+		}
+
+		best.map(|(count, start, end)| {
+			let sample = Sample {
+				center: start + (end - start) / 2,
+				radius: (end - start + 1) / 2,
+			};
+
+			(sample, count)
+		})
+	}
+
+	/// Returns the signed nanoseconds from [`Self::origin`] to `instant`.
+	fn relative(&self, instant: Instant) -> i128 {
+		match instant.checked_duration_since(self.origin) {
+			Some(duration) => duration_to_nanos(duration),
+			None => -duration_to_nanos(self.origin.duration_since(instant)),
+		}
+	}
+}
+
+/// Error returned when adding a sample to [`SyncedClock`] fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SampleError {
+	/// The response was received before the request was sent.
+	NegativeRoundTrip,
+	/// The HTTP `Date` header isn't a valid HTTP date.
+	InvalidDate,
+}
+
+impl Display for SampleError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NegativeRoundTrip => {
+				write!(formatter, "response received before request was sent")
+			}
+			Self::InvalidDate => write!(formatter, "invalid HTTP date"),
+		}
+	}
+}
+
+#[cfg(any(
+	all(target_arch = "wasm32", feature = "std"),
+	all(not(target_arch = "wasm32"), not(target_os = "none"))
+))]
+impl Error for SampleError {}
+
+/// Converts a [`Duration`] to nanoseconds.
+#[allow(clippy::as_conversions, clippy::cast_possible_wrap)]
+const fn duration_to_nanos(duration: Duration) -> i128 {
+	// `Duration` fits into 94 bits.
+	duration.as_nanos() as i128
+}
+
+/// Converts non-negative nanoseconds to a [`Duration`].
+fn nanos_to_duration(nanos: i128) -> Option<Duration> {
+	Some(Duration::new(
+		u64::try_from(nanos / NANOS_PER_SEC).ok()?,
+		u32::try_from(nanos % NANOS_PER_SEC).ok()?,
+	))
+}
+
+/// Converts a [`SystemTime`] to signed nanoseconds since [`UNIX_EPOCH`].
+fn system_time_to_nanos(time: SystemTime) -> i128 {
+	match time.duration_since(UNIX_EPOCH) {
+		Ok(duration) => duration_to_nanos(duration),
+		Err(error) => -duration_to_nanos(error.duration()),
+	}
+}
+
+/// Converts signed nanoseconds since [`UNIX_EPOCH`] to a [`SystemTime`].
+fn nanos_to_system_time(nanos: i128) -> Option<SystemTime> {
+	if nanos >= 0 {
+		UNIX_EPOCH.checked_add(nanos_to_duration(nanos)?)
+	} else {
+		UNIX_EPOCH.checked_sub(nanos_to_duration(-nanos)?)
+	}
+}
@@ -1,4 +1,4 @@
-//! Platform-specific extensions to [`web-time`](crate) for the Web platform.
+//! Web-specific extension to [`web_time::SystemTime`](crate::SystemTime).
 
 #![allow(clippy::absolute_paths)]
 
@@ -8,7 +8,7 @@ use crate::SystemTime;
 
 #[cfg(all(
 	target_arch = "wasm32",
-	any(target_os = "unknown", target_os = "none"),
+	any(target_os = "unknown", target_os = "none", web_time_force_web),
 	not(feature = "std"),
 ))]
 #[doc(hidden)]
@@ -34,7 +34,7 @@ pub trait SystemTimeExt {
 	#[cfg_attr(
 		all(
 			target_arch = "wasm32",
-			any(target_os = "unknown", target_os = "none"),
+			any(target_os = "unknown", target_os = "none", web_time_force_web),
 			not(feature = "std"),
 		),
 		doc = "",
@@ -57,7 +57,7 @@ pub trait SystemTimeExt {
 	#[cfg_attr(
 		all(
 			target_arch = "wasm32",
-			any(target_os = "unknown", target_os = "none"),
+			any(target_os = "unknown", target_os = "none", web_time_force_web),
 			not(feature = "std"),
 		),
 		doc = "",
@@ -0,0 +1,161 @@
+//! Cooperative yielding based on time slices.
+
+use core::num::NonZeroU32;
+use core::time::Duration;
+
+use super::yield_now;
+use crate::clock::{Clock, DefaultClock};
+use crate::Instant;
+
+/// Splits long-running computations into time slices, yielding to the event
+/// loop in between to keep the UI responsive.
+///
+/// A slice starts when the [`TimeSlicer`] is created or reset and ends once
+/// its budget is used up. Checking the time can be limited to every n-th call
+/// of [`TimeSlicer::should_yield()`] with
+/// [`TimeSlicer::with_sample_interval()`], making it cheap enough to be called
+/// in tight loops.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # async fn test() {
+/// use core::num::NonZeroU32;
+///
+/// use web_time::web::TimeSlicer;
+/// use web_time::Duration;
+///
+/// let mut slicer = TimeSlicer::new(Duration::from_millis(8))
+/// 	.with_sample_interval(NonZeroU32::new(100).unwrap());
+///
+/// for item in 0..100_000 {
+/// 	// Process item.
+/// # 	let _ = item;
+/// 	slicer.yield_if_needed().await;
+/// }
+/// # }
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # 	let _future = test();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimeSlicer<C = DefaultClock> {
+	/// Time source.
+	clock: C,
+	/// Length of a slice.
+	budget: Duration,
+	/// End of the current slice, [`None`] if it never ends.
+	deadline: Option<Instant>,
+	/// Number of calls between checking the time, never zero.
+	interval: u32,
+	/// Remaining calls until the time is checked.
+	countdown: u32,
+	/// If the current slice has ended.
+	expired: bool,
+}
+
+impl TimeSlicer {
+	/// Creates a new [`TimeSlicer`] starting a slice of `budget` now.
+	#[must_use]
+	pub fn new(budget: Duration) -> Self {
+		Self::with_clock(DefaultClock, budget)
+	}
+}
+
+impl<C: Clock> TimeSlicer<C> {
+	/// Creates a new [`TimeSlicer`] starting a slice of `budget` now, using
+	/// the given [`Clock`].
+	pub fn with_clock(clock: C, budget: Duration) -> Self {
+		let deadline = clock.now().checked_add(budget);
+
+		Self {
+			clock,
+			budget,
+			deadline,
+			interval: 1,
+			countdown: 1,
+			expired: false,
+		}
+	}
+
+	/// Returns [`true`] if the current slice has ended.
+	///
+	/// Only checks the time every [`TimeSlicer::with_sample_interval()`]
+	/// calls. Keeps returning [`true`] until [`TimeSlicer::reset()`] is called.
+	pub fn should_yield(&mut self) -> bool {
+		if self.expired {
+			return true;
+		}
+
+		self.countdown -= 1;
+
+		if self.countdown == 0 {
+			self.countdown = self.interval;
+
+			if let Some(deadline) = self.deadline {
+				self.expired = self.clock.now() >= deadline;
+			}
+		}
+
+		self.expired
+	}
+
+	/// Yields to the event loop with [`yield_now()`](super::yield_now) and
+	/// starts a new slice if [`TimeSlicer::should_yield()`] returns [`true`].
+	/// Returns [`true`] if it yielded.
+	pub async fn yield_if_needed(&mut self) -> bool {
+		if self.should_yield() {
+			yield_now().await;
+			self.reset();
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Starts a new slice now.
+	pub fn reset(&mut self) {
+		self.deadline = self.clock.now().checked_add(self.budget);
+		self.countdown = self.interval;
+		self.expired = false;
+	}
+
+	/// Returns the time remaining in the current slice, which is zero if it
+	/// has ended.
+	#[must_use]
+	pub fn remaining(&self) -> Duration {
+		match self.deadline {
+			Some(deadline) => deadline.saturating_duration_since(self.clock.now()),
+			None => Duration::MAX,
+		}
+	}
+}
+
+impl<C> TimeSlicer<C> {
+	/// Checks the time only every `calls` calls of
+	/// [`TimeSlicer::should_yield()`] instead of on every call.
+	#[must_use]
+	pub const fn with_sample_interval(mut self, calls: NonZeroU32) -> Self {
+		self.interval = calls.get();
+		self.countdown = calls.get();
+		self
+	}
+
+	/// Returns the [`Clock`] used by this [`TimeSlicer`].
+	pub const fn clock(&self) -> &C {
+		&self.clock
+	}
+
+	/// Returns the length of a slice.
+	#[must_use]
+	pub const fn budget(&self) -> Duration {
+		self.budget
+	}
+}
@@ -0,0 +1,307 @@
+//! Resettable timers backed by [`setTimeout()`].
+//!
+//! With the `std` crate feature, all timers of a thread share a single
+//! [`setTimeout()`] scheduled for the earliest deadline.
+//!
+//! [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+#[cfg(feature = "futures")]
+use futures_core::{FusedStream, Stream};
+
+use crate::time::alarm::Alarm;
+use crate::Instant;
+
+/// A future that completes at a specific [`Instant`], similar to
+/// `futures-timer`'s [`Delay`](https://docs.rs/futures-timer/3/futures_timer/struct.Delay.html).
+///
+/// Resetting a [`Delay`] reuses its registration, so it is cheap enough to be
+/// done on every received message, e.g. for keep-alives.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # async fn test() {
+/// use web_time::web::Delay;
+/// use web_time::{Duration, Instant};
+///
+/// let mut delay = Delay::new(Duration::from_millis(10));
+/// // Received a message, push the deadline back.
+/// delay.reset(Instant::now() + Duration::from_millis(10));
+/// (&mut delay).await;
+/// assert!(delay.deadline() <= Instant::now());
+/// # }
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # 	let _future = test();
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Delay {
+	/// The [`Instant`] at which this future completes.
+	deadline: Instant,
+	/// Wakes the task once [`Self::deadline`] is reached.
+	alarm: Alarm,
+}
+
+impl Delay {
+	/// Creates a new [`Delay`] completing after `duration`.
+	///
+	/// # Panics
+	///
+	/// If the deadline can't be represented by [`Instant`].
+	pub fn new(duration: Duration) -> Self {
+		Self::at(Instant::now() + duration)
+	}
+
+	/// Creates a new [`Delay`] completing at `deadline`.
+	pub const fn at(deadline: Instant) -> Self {
+		Self {
+			deadline,
+			alarm: Alarm::new(),
+		}
+	}
+
+	/// Returns the [`Instant`] at which this [`Delay`] completes.
+	#[must_use]
+	pub const fn deadline(&self) -> Instant {
+		self.deadline
+	}
+
+	/// Resets this [`Delay`] to complete at `deadline`, even if it already
+	/// completed.
+	pub fn reset(&mut self, deadline: Instant) {
+		self.deadline = deadline;
+		self.alarm.reset(deadline);
+	}
+}
+
+impl Future for Delay {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		this.alarm.poll(this.deadline, cx)
+	}
+}
+
+/// A timer firing once or periodically, similar to `async-io`'s
+/// [`Timer`](https://docs.rs/async-io/2/async_io/struct.Timer.html).
+///
+/// As a [`Future`] it completes with the [`Instant`] it was scheduled to fire
+/// at. A periodic [`Timer`] is then re-armed for the next period, so it can be
+/// awaited again by reference.
+#[cfg_attr(
+	feature = "futures",
+	doc = "",
+	doc = "As a [`Stream`] it yields every time it fires and ends when it won't fire again."
+)]
+///
+/// Periods are added to the previous deadline, not to the time it actually
+/// fired, so a periodic [`Timer`] doesn't drift. If multiple periods were
+/// missed, it fires for each one immediately.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # async fn test() {
+/// use web_time::web::Timer;
+/// use web_time::Duration;
+///
+/// let mut timer = Timer::interval(Duration::from_millis(10));
+///
+/// for _ in 0..3 {
+/// 	let instant = (&mut timer).await;
+/// 	// Retransmit.
+/// # 	let _ = instant;
+/// }
+/// # }
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # 	let _future = test();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Timer {
+	/// The next [`Instant`] at which this [`Timer`] fires.
+	when: Option<Instant>,
+	/// The period of a periodic [`Timer`].
+	period: Option<Duration>,
+	/// Wakes the task once [`Self::when`] is reached.
+	alarm: Alarm,
+}
+
+impl Timer {
+	/// Creates a [`Timer`] that never fires.
+	#[must_use]
+	pub const fn never() -> Self {
+		Self {
+			when: None,
+			period: None,
+			alarm: Alarm::new(),
+		}
+	}
+
+	/// Creates a [`Timer`] that fires once after `duration`.
+	///
+	/// Never fires if the deadline can't be represented by [`Instant`].
+	#[must_use]
+	pub fn after(duration: Duration) -> Self {
+		Instant::now()
+			.checked_add(duration)
+			.map_or_else(Self::never, Self::at)
+	}
+
+	/// Creates a [`Timer`] that fires once at `instant`.
+	#[must_use]
+	pub const fn at(instant: Instant) -> Self {
+		Self {
+			when: Some(instant),
+			period: None,
+			alarm: Alarm::new(),
+		}
+	}
+
+	/// Creates a [`Timer`] that fires after `period` and then periodically.
+	///
+	/// # Panics
+	///
+	/// If `period` is zero.
+	#[must_use]
+	pub fn interval(period: Duration) -> Self {
+		Instant::now()
+			.checked_add(period)
+			.map_or_else(Self::never, |start| Self::interval_at(start, period))
+	}
+
+	/// Creates a [`Timer`] that fires at `start` and then every `period`.
+	///
+	/// # Panics
+	///
+	/// If `period` is zero.
+	#[must_use]
+	pub fn interval_at(start: Instant, period: Duration) -> Self {
+		assert!(!period.is_zero(), "`period` must be non-zero");
+
+		Self {
+			when: Some(start),
+			period: Some(period),
+			alarm: Alarm::new(),
+		}
+	}
+
+	/// Returns [`true`] if this [`Timer`] will fire again.
+	#[must_use]
+	pub const fn will_fire(&self) -> bool {
+		self.when.is_some()
+	}
+
+	/// Sets this [`Timer`] to fire once after `duration`.
+	///
+	/// Never fires if the deadline can't be represented by [`Instant`].
+	pub fn set_after(&mut self, duration: Duration) {
+		self.set(Instant::now().checked_add(duration), None);
+	}
+
+	/// Sets this [`Timer`] to fire once at `instant`.
+	pub fn set_at(&mut self, instant: Instant) {
+		self.set(Some(instant), None);
+	}
+
+	/// Sets this [`Timer`] to fire after `period` and then periodically.
+	///
+	/// # Panics
+	///
+	/// If `period` is zero.
+	pub fn set_interval(&mut self, period: Duration) {
+		assert!(!period.is_zero(), "`period` must be non-zero");
+
+		self.set(Instant::now().checked_add(period), Some(period));
+	}
+
+	/// Sets this [`Timer`] to fire at `start` and then every `period`.
+	///
+	/// # Panics
+	///
+	/// If `period` is zero.
+	pub fn set_interval_at(&mut self, start: Instant, period: Duration) {
+		assert!(!period.is_zero(), "`period` must be non-zero");
+
+		self.set(Some(start), Some(period));
+	}
+
+	/// Sets when this [`Timer`] fires next and its period.
+	fn set(&mut self, when: Option<Instant>, period: Option<Duration>) {
+		self.when = when;
+		self.period = period;
+
+		match when {
+			Some(when) => self.alarm.reset(when),
+			None => self.alarm.cancel(),
+		}
+	}
+
+	/// Polls for the next time this [`Timer`] fires. Returns [`None`] if it
+	/// won't fire again.
+	fn poll_fire(&mut self, cx: &Context<'_>) -> Poll<Option<Instant>> {
+		let when = match self.when {
+			Some(when) => when,
+			None => return Poll::Ready(None),
+		};
+
+		if self.alarm.poll(when, cx).is_pending() {
+			return Poll::Pending;
+		}
+
+		self.when = self.period.and_then(|period| when.checked_add(period));
+
+		Poll::Ready(Some(when))
+	}
+}
+
+impl Future for Timer {
+	type Output = Instant;
+
+	/// Never completes if the [`Timer`] won't fire again.
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		match self.get_mut().poll_fire(cx) {
+			Poll::Ready(Some(when)) => Poll::Ready(when),
+			Poll::Ready(None) | Poll::Pending => Poll::Pending,
+		}
+	}
+}
+
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "futures")))]
+impl Stream for Timer {
+	type Item = Instant;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.get_mut().poll_fire(cx)
+	}
+}
+
+#[cfg(feature = "futures")]
+#[cfg_attr(all(doc, docsrs), doc(cfg(feature = "futures")))]
+impl FusedStream for Timer {
+	fn is_terminated(&self) -> bool {
+		!self.will_fire()
+	}
+}
@@ -0,0 +1,652 @@
+//! Hierarchical timer wheel.
+//!
+//! Timers are kept in [`LEVELS`] levels of [`SLOTS`] slots each, where a slot
+//! of the first level covers a single millisecond and a slot of every further
+//! level covers a whole previous level. Each slot holds an intrusive
+//! doubly-linked list of timers, making insertion and cancellation O(1). When a
+//! slot of a higher level is reached, its timers cascade down into the lower
+//! levels until they expire.
+//!
+//! See also Varghese and Lauck's [Hashed and Hierarchical Timing Wheels] and
+//! [`tokio`'s implementation].
+//!
+//! [Hashed and Hierarchical Timing Wheels]: https://doi.org/10.1145/41457.37504
+//! [`tokio`'s implementation]: https://github.com/tokio-rs/tokio/blob/tokio-1.43.0/tokio/src/runtime/time/wheel/mod.rs
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+
+use crate::clock::{Clock, DefaultClock};
+use crate::time::alarm::Alarm;
+use crate::Instant;
+
+/// Number of levels.
+const LEVELS: usize = 6;
+
+/// Number of bits needed to address a slot in a level.
+const SLOT_BITS: usize = 6;
+
+/// Number of slots per level.
+const SLOTS: usize = 1 << SLOT_BITS;
+
+/// Mask to get the slot of a tick in the first level.
+const SLOT_MASK: u64 = (1 << SLOT_BITS) - 1;
+
+/// Number of ticks covered by all levels, `SLOTS ^ LEVELS`. Timers further in
+/// the future are placed in the last level and cascaded until they are in
+/// range.
+const MAX_TICKS: u64 = 1 << (SLOT_BITS * LEVELS);
+
+/// Multiplexes many deadlines onto a single [`setTimeout()`], similar to the
+/// timer driver of `tokio`.
+///
+/// Each timer is registered with a [`Waker`], which is woken once its deadline
+/// is reached. Inserting and cancelling a timer is O(1), which makes this
+/// suitable for thousands of short-lived timers, e.g. per-entity timeouts.
+/// Deadlines are tracked with millisecond precision, but a timer is never
+/// woken before its deadline.
+///
+/// Time is only advanced by [`TimerWheel::process()`]. With the default
+/// [`Clock`], [`TimerWheel::poll_process()`] keeps a single [`setTimeout()`]
+/// armed for the earliest deadline and can be used to drive the wheel from a
+/// dedicated task. With a manually controlled [`Clock`], e.g. in tests, calling
+/// [`TimerWheel::process()`] after advancing the clock makes timers expire
+/// deterministically.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # async fn test() {
+/// use core::cell::Cell;
+/// use core::future::poll_fn;
+/// use core::task::Poll;
+///
+/// use web_time::web::TimerWheel;
+/// use web_time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// let offset = Cell::new(Duration::ZERO);
+/// let mut wheel = TimerWheel::with_clock(|| start + offset.get());
+///
+/// poll_fn(|cx| {
+/// 	let key = wheel.insert(start + Duration::from_secs(1), cx.waker());
+/// 	assert!(wheel.poll_timer(key, cx).is_pending());
+///
+/// 	offset.set(Duration::from_secs(1));
+/// 	assert_eq!(wheel.process(), 1);
+/// 	assert!(wheel.poll_timer(key, cx).is_ready());
+///
+/// 	Poll::Ready(())
+/// })
+/// .await;
+/// # }
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # 	let _future = test();
+/// # }
+/// ```
+///
+/// [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+pub struct TimerWheel<C = DefaultClock> {
+	/// Time source.
+	clock: C,
+	/// Point in time all ticks are relative to.
+	origin: Instant,
+	/// Ticks processed so far.
+	elapsed: u64,
+	/// Levels of slots.
+	levels: [Level; LEVELS],
+	/// Timers that already expired when they were inserted.
+	pending: Option<usize>,
+	/// Storage of all timers.
+	nodes: Vec<Node>,
+	/// First vacant [`Node`].
+	free: Option<usize>,
+	/// Number of registered timers.
+	len: usize,
+	/// Wakes the driving task at the next expiration.
+	alarm: Alarm,
+	/// Tick the [`Alarm`] is armed for.
+	armed: Option<u64>,
+	/// Task driving this wheel with [`TimerWheel::poll_process()`].
+	driver: Option<Waker>,
+}
+
+/// Identifies a timer registered in a [`TimerWheel`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TimerKey {
+	/// Index of the [`Node`].
+	index: usize,
+	/// Generation of the [`Node`], to detect reuse.
+	generation: u64,
+}
+
+/// A level of the [`TimerWheel`].
+#[derive(Clone, Copy, Debug)]
+struct Level {
+	/// Bitset of slots holding timers.
+	occupied: u64,
+	/// First timer of each slot.
+	slots: [Option<usize>; SLOTS],
+}
+
+/// Storage of a timer.
+#[derive(Debug)]
+struct Node {
+	/// Incremented every time this [`Node`] is vacated.
+	generation: u64,
+	/// The timer, if this [`Node`] is occupied.
+	entry: Option<Entry>,
+	/// Previous timer in the same list.
+	prev: Option<usize>,
+	/// Next timer in the same list, or next vacant [`Node`].
+	next: Option<usize>,
+}
+
+/// A registered timer.
+#[derive(Debug)]
+struct Entry {
+	/// Requested deadline.
+	deadline: Instant,
+	/// [`Self::deadline`] in ticks, rounded up.
+	tick: u64,
+	/// Woken when the deadline is reached.
+	waker: Waker,
+	/// List this timer is linked into.
+	list: List,
+}
+
+/// Identifies a list of timers.
+#[derive(Clone, Copy, Debug)]
+enum List {
+	/// A slot of a [`Level`].
+	Slot {
+		/// Index of the [`Level`].
+		level: usize,
+		/// Index of the slot.
+		slot: usize,
+	},
+	/// [`TimerWheel::pending`].
+	Pending,
+}
+
+impl Level {
+	/// An empty [`Level`].
+	const EMPTY: Self = Self {
+		occupied: 0,
+		slots: [None; SLOTS],
+	};
+
+	/// Returns the tick at which the next occupied slot of this level expires.
+	fn next_expiration(&self, level: usize, elapsed: u64) -> Option<u64> {
+		if self.occupied == 0 {
+			return None;
+		}
+
+		let slot_ticks = 1_u64 << (SLOT_BITS * level);
+		let level_ticks = slot_ticks << SLOT_BITS;
+		let current = (elapsed >> (SLOT_BITS * level)) & SLOT_MASK;
+
+		// Search from the current slot onwards, wrapping around to the start.
+		let ahead = self.occupied & (u64::MAX << current);
+		let slot = if ahead == 0 {
+			self.occupied.trailing_zeros()
+		} else {
+			ahead.trailing_zeros()
+		};
+
+		let start = elapsed & !(level_ticks - 1);
+		let tick = start + u64::from(slot) * slot_ticks;
+
+		// Slots before the current one belong to the next rotation. This can
+		// only happen in the last level, where timers out of range are placed.
+		Some(if tick <= elapsed {
+			tick.saturating_add(level_ticks)
+		} else {
+			tick
+		})
+	}
+}
+
+impl Node {
+	/// Returns the [`Entry`] of this occupied [`Node`].
+	fn entry(&self) -> &Entry {
+		self.entry.as_ref().expect("found vacant node")
+	}
+
+	/// Returns the [`Entry`] of this occupied [`Node`].
+	fn entry_mut(&mut self) -> &mut Entry {
+		self.entry.as_mut().expect("found vacant node")
+	}
+}
+
+impl TimerWheel {
+	/// Creates a new empty [`TimerWheel`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::with_clock(DefaultClock)
+	}
+
+	/// Processes expired timers like [`TimerWheel::process()`] and returns the
+	/// number of woken timers.
+	///
+	/// If none expired, arms a single [`setTimeout()`] for the next expiration
+	/// and makes sure the [`Waker`] in `cx` is woken when it's reached or an
+	/// earlier timer is inserted.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+	/// #
+	/// # #[cfg(target_arch = "wasm32")]
+	/// # use tests_web as _;
+	/// #
+	/// # async fn test() {
+	/// use core::future::poll_fn;
+	/// use core::task::Poll;
+	///
+	/// use web_time::web::TimerWheel;
+	/// use web_time::{Duration, Instant};
+	///
+	/// let mut wheel = TimerWheel::new();
+	/// let deadline = Instant::now() + Duration::from_millis(10);
+	/// let key = poll_fn(|cx| Poll::Ready(wheel.insert(deadline, cx.waker()))).await;
+	///
+	/// poll_fn(|cx| wheel.poll_process(cx)).await;
+	/// assert!(Instant::now() >= deadline);
+	/// assert_eq!(wheel.deadline(key), None);
+	/// # }
+	/// #
+	/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+	/// # fn main() {
+	/// # 	let _future = test();
+	/// # }
+	/// ```
+	///
+	/// [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+	pub fn poll_process(&mut self, cx: &Context<'_>) -> Poll<usize> {
+		loop {
+			let fired = self.process();
+
+			if fired != 0 {
+				return Poll::Ready(fired);
+			}
+
+			match &self.driver {
+				Some(driver) if driver.will_wake(cx.waker()) => (),
+				_ => self.driver = Some(cx.waker().clone()),
+			}
+
+			match self.next_tick().map(|tick| (tick, self.instant(tick))) {
+				Some((tick, Some(deadline))) => {
+					if self.alarm.poll(deadline, cx).is_pending() {
+						self.armed = Some(tick);
+						return Poll::Pending;
+					}
+				}
+				// Too far in the future to ever be reached.
+				Some((tick, None)) => {
+					self.alarm.cancel();
+					self.armed = Some(tick);
+					return Poll::Pending;
+				}
+				None => {
+					self.alarm.cancel();
+					self.armed = None;
+					return Poll::Pending;
+				}
+			}
+		}
+	}
+}
+
+impl Default for TimerWheel {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<C: Clock> TimerWheel<C> {
+	/// Creates a new empty [`TimerWheel`] using the given [`Clock`].
+	pub fn with_clock(clock: C) -> Self {
+		let origin = clock.now();
+
+		Self {
+			clock,
+			origin,
+			elapsed: 0,
+			levels: [Level::EMPTY; LEVELS],
+			pending: None,
+			nodes: Vec::new(),
+			free: None,
+			len: 0,
+			alarm: Alarm::new(),
+			armed: None,
+			driver: None,
+		}
+	}
+
+	/// Wakes all timers whose deadline was reached according to the [`Clock`]
+	/// and returns their number. Woken timers are removed.
+	pub fn process(&mut self) -> usize {
+		let now = self.clock.now().saturating_duration_since(self.origin);
+		let now = u64::try_from(now.as_millis()).unwrap_or(u64::MAX);
+		let mut fired = 0;
+
+		let mut next = self.pending.take();
+
+		while let Some(index) = next {
+			next = self.node_mut(index).next;
+			self.fire(index);
+			fired += 1;
+		}
+
+		while let Some((level, tick)) = self.next_slot() {
+			if tick > now {
+				break;
+			}
+
+			self.elapsed = tick;
+			let level_mut = self.level_mut(level);
+			let slot = slot_for(level, tick);
+			level_mut.occupied &= !(1 << slot);
+			let mut next = level_mut.slots.get_mut(slot).and_then(Option::take);
+
+			while let Some(index) = next {
+				next = self.node_mut(index).next;
+				let tick = self.node_mut(index).entry().tick;
+
+				if tick <= self.elapsed {
+					self.fire(index);
+					fired += 1;
+				} else {
+					self.link(index, tick);
+				}
+			}
+		}
+
+		self.elapsed = self.elapsed.max(now);
+
+		fired
+	}
+
+	/// Returns the [`Instant`] at which [`TimerWheel::process()`] has to be
+	/// called next. This can be earlier than the earliest deadline, as timers
+	/// are moved between levels.
+	#[must_use]
+	pub fn next_expiration(&self) -> Option<Instant> {
+		self.next_tick().and_then(|tick| self.instant(tick))
+	}
+
+	/// Registers a timer waking `waker` once `deadline` is reached.
+	pub fn insert(&mut self, deadline: Instant, waker: &Waker) -> TimerKey {
+		let duration = deadline.saturating_duration_since(self.origin);
+		// Round up to the next millisecond to not expire too early.
+		let tick = duration.as_nanos().saturating_add(999_999) / 1_000_000;
+		let tick = u64::try_from(tick).unwrap_or(u64::MAX);
+
+		let entry = Entry {
+			deadline,
+			tick,
+			waker: waker.clone(),
+			list: List::Pending,
+		};
+
+		let index = if let Some(index) = self.free {
+			let node = self.node_mut(index);
+			node.entry = Some(entry);
+			let next = node.next;
+			self.free = next;
+			index
+		} else {
+			self.nodes.push(Node {
+				generation: 0,
+				entry: Some(entry),
+				prev: None,
+				next: None,
+			});
+			self.nodes.len() - 1
+		};
+
+		self.link(index, tick);
+		self.len += 1;
+
+		if self.armed.map_or(true, |armed| tick < armed) {
+			if let Some(driver) = self.driver.take() {
+				driver.wake();
+			}
+		}
+
+		TimerKey {
+			index,
+			generation: self.node_mut(index).generation,
+		}
+	}
+
+	/// Returns [`Poll::Ready`] if the timer expired and removes it, otherwise
+	/// makes sure the [`Waker`] in `cx` is woken instead of the registered one.
+	///
+	/// Timers that were already woken by [`TimerWheel::process()`] or
+	/// cancelled are considered expired.
+	pub fn poll_timer(&mut self, key: TimerKey, cx: &Context<'_>) -> Poll<()> {
+		let now = self.clock.now();
+
+		let entry = match self.entry_mut(key) {
+			Some(entry) => entry,
+			None => return Poll::Ready(()),
+		};
+
+		if entry.deadline <= now {
+			self.cancel(key);
+			Poll::Ready(())
+		} else {
+			if !entry.waker.will_wake(cx.waker()) {
+				entry.waker.clone_from(cx.waker());
+			}
+
+			Poll::Pending
+		}
+	}
+
+	/// Removes the timer without waking it. Returns [`false`] if it was
+	/// already woken or cancelled.
+	pub fn cancel(&mut self, key: TimerKey) -> bool {
+		if self.entry_mut(key).is_none() {
+			return false;
+		}
+
+		self.unlink(key.index);
+		drop(self.release(key.index));
+
+		true
+	}
+}
+
+impl<C> TimerWheel<C> {
+	/// Returns the [`Clock`] used by this [`TimerWheel`].
+	pub const fn clock(&self) -> &C {
+		&self.clock
+	}
+
+	/// Returns the number of registered timers.
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns [`true`] if no timers are registered.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the deadline of the timer, if it wasn't woken or cancelled yet.
+	#[must_use]
+	pub fn deadline(&self, key: TimerKey) -> Option<Instant> {
+		self.nodes
+			.get(key.index)
+			.filter(|node| node.generation == key.generation)
+			.and_then(|node| node.entry.as_ref())
+			.map(|entry| entry.deadline)
+	}
+
+	/// Returns the tick of the next expiration.
+	fn next_tick(&self) -> Option<u64> {
+		if self.pending.is_some() {
+			return Some(self.elapsed);
+		}
+
+		self.next_slot().map(|(_, tick)| tick)
+	}
+
+	/// Returns the level and tick of the next slot to expire.
+	fn next_slot(&self) -> Option<(usize, u64)> {
+		// Slots of lower levels always expire before those of higher levels.
+		self.levels
+			.iter()
+			.enumerate()
+			.find_map(|(level, level_ref)| {
+				level_ref
+					.next_expiration(level, self.elapsed)
+					.map(|tick| (level, tick))
+			})
+	}
+
+	/// Converts a tick to an [`Instant`].
+	fn instant(&self, tick: u64) -> Option<Instant> {
+		self.origin.checked_add(Duration::from_millis(tick))
+	}
+
+	/// Returns the [`Entry`] of the given [`TimerKey`].
+	fn entry_mut(&mut self, key: TimerKey) -> Option<&mut Entry> {
+		self.nodes
+			.get_mut(key.index)
+			.filter(|node| node.generation == key.generation)
+			.and_then(|node| node.entry.as_mut())
+	}
+
+	/// Returns the [`Node`] at `index`.
+	fn node_mut(&mut self, index: usize) -> &mut Node {
+		self.nodes.get_mut(index).expect("found invalid node index")
+	}
+
+	/// Returns the [`Level`] at `level`.
+	fn level_mut(&mut self, level: usize) -> &mut Level {
+		self.levels.get_mut(level).expect("found invalid level")
+	}
+
+	/// Returns the first timer of the given [`List`].
+	fn head_mut(&mut self, list: List) -> &mut Option<usize> {
+		match list {
+			List::Slot { level, slot } => self
+				.level_mut(level)
+				.slots
+				.get_mut(slot)
+				.expect("found invalid slot"),
+			List::Pending => &mut self.pending,
+		}
+	}
+
+	/// Links the timer at `index` into the [`List`] matching `tick`.
+	fn link(&mut self, index: usize, tick: u64) {
+		let list = if tick <= self.elapsed {
+			List::Pending
+		} else {
+			let level = level_for(self.elapsed, tick);
+			let slot = slot_for(level, tick);
+			self.level_mut(level).occupied |= 1 << slot;
+			List::Slot { level, slot }
+		};
+
+		let next = self.head_mut(list).replace(index);
+
+		if let Some(next) = next {
+			self.node_mut(next).prev = Some(index);
+		}
+
+		let node = self.node_mut(index);
+		node.prev = None;
+		node.next = next;
+		node.entry_mut().list = list;
+	}
+
+	/// Unlinks the timer at `index` from its [`List`].
+	fn unlink(&mut self, index: usize) {
+		let node = self.node_mut(index);
+		let prev = node.prev.take();
+		let next = node.next.take();
+		let list = node.entry().list;
+
+		if let Some(prev) = prev {
+			self.node_mut(prev).next = next;
+		} else {
+			*self.head_mut(list) = next;
+
+			if let (List::Slot { level, slot }, None) = (list, next) {
+				self.level_mut(level).occupied &= !(1 << slot);
+			}
+		}
+
+		if let Some(next) = next {
+			self.node_mut(next).prev = prev;
+		}
+	}
+
+	/// Vacates the [`Node`] at `index` and returns its [`Entry`]. The timer
+	/// must not be linked into a [`List`] anymore.
+	fn release(&mut self, index: usize) -> Entry {
+		let free = self.free.replace(index);
+		let node = self.node_mut(index);
+		node.generation = node.generation.wrapping_add(1);
+		node.prev = None;
+		node.next = free;
+		let entry = node.entry.take().expect("found vacant node");
+		self.len -= 1;
+
+		entry
+	}
+
+	/// Wakes and removes the timer at `index`, which must not be linked into a
+	/// [`List`] anymore.
+	fn fire(&mut self, index: usize) {
+		self.release(index).waker.wake();
+	}
+}
+
+impl<C: Debug> Debug for TimerWheel<C> {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter
+			.debug_struct("TimerWheel")
+			.field("clock", &self.clock)
+			.field("origin", &self.origin)
+			.field("elapsed", &self.elapsed)
+			.field("len", &self.len)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Returns the level a timer expiring at `tick` is placed in.
+fn level_for(elapsed: u64, tick: u64) -> usize {
+	// The highest bit differing from `elapsed` determines the level.
+	let masked = ((elapsed ^ tick) | SLOT_MASK).min(MAX_TICKS - 1);
+	let significant = u64::BITS - 1 - masked.leading_zeros();
+
+	usize::try_from(significant).unwrap_or_default() / SLOT_BITS
+}
+
+/// Returns the slot of `level` a timer expiring at `tick` is placed in.
+fn slot_for(level: usize, tick: u64) -> usize {
+	// Masked to the number of slots, so it always fits.
+	usize::try_from((tick >> (SLOT_BITS * level)) & SLOT_MASK).unwrap_or_default()
+}
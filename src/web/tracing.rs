@@ -0,0 +1,133 @@
+//! Integration with [`tracing-subscriber`].
+//!
+//! [`tracing-subscriber`]'s own timers use [`std::time::SystemTime::now()`],
+//! which panics in browsers. [`Rfc3339`] and [`Uptime`] are drop-in
+//! replacements based on [`SystemTime`] and [`Instant`]. Additionally
+//! [`PerformanceLayer`] reports spans and events to the browsers performance
+//! tools via [`profiling`].
+//!
+//! # Example
+//!
+//! ```
+//! # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+//! #
+//! # #[cfg(target_arch = "wasm32")]
+//! # use tests_web as _;
+//! use tracing_subscriber::layer::SubscriberExt;
+//! use web_time::web::tracing::{PerformanceLayer, Rfc3339};
+//!
+//! # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+//! # fn main() {
+//! let subscriber = tracing_subscriber::registry()
+//! 	.with(tracing_subscriber::fmt::layer().with_timer(Rfc3339))
+//! 	.with(PerformanceLayer);
+//! # let _ = subscriber;
+//! # }
+//! ```
+//!
+//! [`tracing-subscriber`]: https://docs.rs/tracing-subscriber/0.3
+//! [`std::time::SystemTime::now()`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html#method.now
+
+use core::fmt;
+
+use tracing_core::span::Id;
+use tracing_core::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use super::profiling;
+use crate::{Instant, SystemTime, UtcDateTime};
+
+/// Formats [`SystemTime::now()`] as a [RFC 3339] timestamp in UTC with
+/// microsecond precision, e.g. `2001-07-08T00:34:59.026490Z`.
+///
+/// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Rfc3339;
+
+impl FormatTime for Rfc3339 {
+	fn format_time(&self, writer: &mut Writer<'_>) -> fmt::Result {
+		let date_time = UtcDateTime::from(SystemTime::now());
+
+		write!(
+			writer,
+			"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+			date_time.year(),
+			date_time.month(),
+			date_time.day(),
+			date_time.hour(),
+			date_time.minute(),
+			date_time.second(),
+			date_time.nanosecond() / 1000,
+		)
+	}
+}
+
+/// Formats the time elapsed since the given [`Instant`], e.g.
+/// `1.000000000s`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Uptime(Instant);
+
+impl Default for Uptime {
+	/// Creates a new [`Uptime`] starting from [`Instant::now()`].
+	fn default() -> Self {
+		Self(Instant::now())
+	}
+}
+
+impl From<Instant> for Uptime {
+	fn from(epoch: Instant) -> Self {
+		Self(epoch)
+	}
+}
+
+impl FormatTime for Uptime {
+	fn format_time(&self, writer: &mut Writer<'_>) -> fmt::Result {
+		let elapsed = self.0.elapsed();
+		write!(
+			writer,
+			"{:4}.{:09}s",
+			elapsed.as_secs(),
+			elapsed.subsec_nanos()
+		)
+	}
+}
+
+/// [`Layer`] creating a [`profiling::measure()`] for every time a span is
+/// entered and exited and a [`profiling::mark()`] for every event.
+///
+/// Requires a [`Subscriber`] implementing [`LookupSpan`], e.g.
+/// [`Registry`](tracing_subscriber::Registry).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PerformanceLayer;
+
+/// Stored in the span extensions to mark when the span was entered.
+struct Entered(Instant);
+
+impl<S> Layer<S> for PerformanceLayer
+where
+	S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+	fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id) {
+			span.extensions_mut().replace(Entered(Instant::now()));
+		}
+	}
+
+	fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id) {
+			let entered = span.extensions_mut().remove();
+
+			if let Some(Entered(start)) = entered {
+				profiling::measure(span.name(), start, Instant::now());
+			}
+		}
+	}
+
+	fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+		profiling::mark(event.metadata().name());
+	}
+}
@@ -0,0 +1,147 @@
+//! Yielding to the event loop.
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
+
+use crate::time::js::{self, MessageChannel};
+
+/// Yields to the event loop, giving the browser a chance to render and handle
+/// input.
+///
+/// Uses the first available of:
+/// - [`scheduler.yield()`], which continues before other queued tasks.
+/// - [`MessageChannel`], which isn't subject to the clamping of
+///   [`setTimeout()`].
+/// - [`setTimeout()`] with a delay of zero.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std, no_main)]
+/// #
+/// # #[cfg(target_arch = "wasm32")]
+/// # use tests_web as _;
+/// #
+/// # async fn test() {
+/// use web_time::web;
+///
+/// for chunk in 0..10 {
+/// 	// Process chunk.
+/// # 	let _ = chunk;
+/// 	web::yield_now().await;
+/// }
+/// # }
+/// #
+/// # #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+/// # fn main() {
+/// # 	let _future = test();
+/// # }
+/// ```
+///
+/// [`scheduler.yield()`]: https://developer.mozilla.org/en-US/docs/Web/API/Scheduler/yield
+/// [`MessageChannel`]: https://developer.mozilla.org/en-US/docs/Web/API/MessageChannel
+/// [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/setTimeout
+pub const fn yield_now() -> YieldNow {
+	YieldNow { shared: None }
+}
+
+/// Future returned by [`yield_now()`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct YieldNow {
+	/// State shared with the callback, if polled at least once.
+	shared: Option<Rc<RefCell<Shared>>>,
+}
+
+/// State shared between [`YieldNow`] and its callback.
+#[derive(Debug)]
+struct Shared {
+	/// Set once the callback was called.
+	done: bool,
+	/// Woken once the callback was called.
+	waker: Option<Waker>,
+}
+
+impl YieldNow {
+	/// Schedules the callback.
+	///
+	/// The callback is freed after it was called, so it doesn't have to be
+	/// cancelled if [`YieldNow`] is dropped early.
+	fn register(shared: Rc<RefCell<Shared>>) {
+		let complete = move || {
+			let waker = {
+				let mut shared = shared.borrow_mut();
+				shared.done = true;
+				shared.waker.take()
+			};
+
+			if let Some(waker) = waker {
+				waker.wake();
+			}
+		};
+
+		let scheduler = js::SCHEDULER.with(|scheduler| {
+			scheduler
+				.as_ref()
+				.filter(|scheduler| scheduler.yield_fn().is_function())
+				.map(js::Scheduler::yield_now)
+		});
+
+		if let Some(promise) = scheduler {
+			promise.then(&Closure::once_into_js(move |_: JsValue| complete()));
+		} else if js::MESSAGE_CHANNEL.with(JsValue::is_function) {
+			let channel = MessageChannel::new();
+			let port = channel.port1();
+			let callback = {
+				let port = port.clone();
+				Closure::once_into_js(move |_: JsValue| {
+					// Otherwise the port keeps e.g. Node.js alive.
+					port.close();
+					complete();
+				})
+			};
+			port.set_onmessage(&callback);
+			channel.port2().post_message(&JsValue::UNDEFINED);
+		} else {
+			js::set_timeout_once(&Closure::once_into_js(complete), 0);
+		}
+	}
+}
+
+impl Future for YieldNow {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+
+		if let Some(shared) = &this.shared {
+			let mut shared = shared.borrow_mut();
+
+			if shared.done {
+				return Poll::Ready(());
+			}
+
+			match &shared.waker {
+				Some(waker) if waker.will_wake(cx.waker()) => (),
+				_ => shared.waker = Some(cx.waker().clone()),
+			}
+		} else {
+			let shared = Rc::new(RefCell::new(Shared {
+				done: false,
+				waker: Some(cx.waker().clone()),
+			}));
+			Self::register(Rc::clone(&shared));
+			this.shared = Some(shared);
+		}
+
+		Poll::Pending
+	}
+}
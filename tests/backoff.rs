@@ -0,0 +1,219 @@
+//! [`Backoff`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+use core::iter::FusedIterator;
+
+use static_assertions::assert_impl_all;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Backoff, DefaultRandom, Duration, RandomSource};
+
+use self::util::ManualClock;
+
+/// Shorthand for [`Duration::from_millis()`].
+const fn ms(millis: u64) -> Duration {
+	Duration::from_millis(millis)
+}
+
+/// [`Backoff`] and [`DefaultRandom`] implement common traits.
+#[wasm_bindgen_test(unsupported = test)]
+const fn traits() {
+	assert_impl_all!(Backoff: Clone, FusedIterator, Send, Sync);
+	assert_impl_all!(DefaultRandom: Clone, Default, RandomSource, Send, Sync);
+}
+
+/// [`Backoff::exponential()`] doubles every delay.
+#[wasm_bindgen_test(unsupported = test)]
+fn exponential() {
+	let mut backoff = Backoff::exponential(ms(100))
+		.with_max_delay(ms(1000))
+		.with_max_retries(7);
+
+	assert!(backoff.by_ref().eq([
+		ms(100),
+		ms(200),
+		ms(400),
+		ms(800),
+		ms(1000),
+		ms(1000),
+		ms(1000)
+	]));
+	assert_eq!(backoff.retries(), 7);
+	assert_eq!(backoff.next(), None);
+
+	backoff.reset();
+	assert_eq!(backoff.retries(), 0);
+	assert_eq!(backoff.next(), Some(ms(100)));
+
+	// Doesn't overflow.
+	let mut backoff = Backoff::exponential(Duration::MAX);
+	assert_eq!(backoff.next(), Some(Duration::MAX));
+	assert_eq!(backoff.next(), Some(Duration::MAX));
+}
+
+/// [`Backoff::decorrelated_jitter()`] chooses delays between the initial and
+/// three times the previous delay.
+#[wasm_bindgen_test(unsupported = test)]
+fn decorrelated_jitter() {
+	// The lowest random numbers always choose the initial delay.
+	let backoff = Backoff::decorrelated_jitter(ms(100))
+		.with_random(|| 0)
+		.with_max_retries(3);
+	assert!(backoff.eq([ms(100), ms(100), ms(100)]));
+
+	// The highest random numbers triple the delay.
+	let mut previous = ms(100);
+	let random = move || {
+		let range = u64::try_from((previous * 3).saturating_sub(ms(100)).as_nanos()).unwrap();
+		previous = (previous * 3).min(ms(1000));
+		range
+	};
+	let backoff = Backoff::decorrelated_jitter(ms(100))
+		.with_random(random)
+		.with_max_delay(ms(1000))
+		.with_max_retries(4);
+	assert!(backoff.eq([ms(300), ms(900), ms(1000), ms(1000)]));
+
+	// Stays in bounds with a real random source.
+	let backoff = Backoff::decorrelated_jitter(ms(100))
+		.with_random(DefaultRandom::from_seed(42))
+		.with_max_delay(ms(5000))
+		.with_max_retries(100);
+	let mut previous = ms(100);
+
+	for delay in backoff {
+		assert!(delay >= ms(100), "{delay:?}");
+		assert!(delay <= (previous * 3).min(ms(5000)), "{delay:?}");
+		previous = delay;
+	}
+}
+
+/// [`DefaultRandom::from_seed()`] is deterministic.
+#[wasm_bindgen_test(unsupported = test)]
+fn seed() {
+	let backoff = || {
+		Backoff::decorrelated_jitter(ms(100))
+			.with_random(DefaultRandom::from_seed(42))
+			.with_max_retries(10)
+	};
+
+	assert!(backoff().eq(backoff()));
+
+	let mut first = DefaultRandom::from_seed(0);
+	let mut second = DefaultRandom::from_seed(1);
+	assert_ne!(first.next_u64(), second.next_u64());
+}
+
+/// [`Backoff::with_max_elapsed()`] ends before exceeding the elapsed time.
+#[wasm_bindgen_test(unsupported = test)]
+fn max_elapsed() {
+	let clock = ManualClock::new();
+	let mut backoff = Backoff::exponential(ms(100))
+		.with_max_elapsed(ms(1000))
+		.with_clock(&clock);
+
+	for expected in [ms(100), ms(200), ms(400)] {
+		let delay = backoff.next().unwrap();
+		assert_eq!(delay, expected);
+		clock.advance(delay);
+	}
+
+	// The next retry would happen after 1.5s.
+	assert_eq!(backoff.next(), None);
+	assert_eq!(backoff.retries(), 3);
+
+	// Restarts the elapsed time.
+	backoff.reset();
+	assert_eq!(backoff.deadline(), Some(clock.now() + ms(1000)));
+	assert_eq!(backoff.next(), Some(ms(100)));
+}
+
+/// [`Backoff::with_deadline()`] ends before exceeding the deadline.
+#[wasm_bindgen_test(unsupported = test)]
+fn deadline() {
+	let clock = ManualClock::new();
+	let deadline = clock.now() + ms(500);
+	let mut backoff = Backoff::exponential(ms(100))
+		.with_deadline(deadline)
+		.with_max_elapsed(ms(1000))
+		.with_clock(&clock);
+	assert_eq!(backoff.deadline(), Some(deadline));
+
+	assert_eq!(backoff.next(), Some(ms(100)));
+	clock.advance(ms(100));
+	assert_eq!(backoff.next(), Some(ms(200)));
+	clock.advance(ms(200));
+
+	// The next retry would happen after 700ms.
+	assert_eq!(backoff.next(), None);
+	assert_eq!(backoff.next(), None);
+
+	// The deadline isn't restarted.
+	backoff.reset();
+	assert_eq!(backoff.next(), Some(ms(100)));
+	clock.advance(ms(100));
+	assert_eq!(backoff.next(), None);
+}
+
+/// Ending iteration doesn't change the state of [`Backoff`], even if a later
+/// delay would meet the deadline.
+#[wasm_bindgen_test(unsupported = test)]
+fn fused() {
+	let clock = ManualClock::new();
+	// The first delay is 300ms, any later one 100ms.
+	let mut values = [u64::try_from(ms(200).as_nanos()).unwrap(), 0].into_iter();
+	let mut backoff = Backoff::decorrelated_jitter(ms(100))
+		.with_random(move || values.next().unwrap())
+		.with_deadline(clock.now() + ms(200))
+		.with_clock(&clock);
+
+	assert_eq!(backoff.next(), None);
+	assert_eq!(backoff.next(), None);
+	assert_eq!(backoff.retries(), 0);
+
+	backoff.reset();
+	assert_eq!(backoff.next(), Some(ms(100)));
+	assert_eq!(backoff.retries(), 1);
+}
+
+/// [`web::retry()`](web_time::web::retry) retries until success or the
+/// [`Backoff`] is exhausted.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+async fn retry() {
+	use core::cell::Cell;
+
+	use web_time::{web, Instant};
+
+	use self::util::{DIFF, MAX_DIFF, WAIT};
+
+	let attempts = Cell::new(0);
+	let op = || {
+		attempts.set(attempts.get() + 1);
+		let attempt = attempts.get();
+		async move {
+			if attempt < 3 {
+				Err(attempt)
+			} else {
+				Ok(attempt)
+			}
+		}
+	};
+
+	let start = Instant::now();
+	let result = web::retry(op, Backoff::exponential(WAIT / 2)).await;
+	assert_eq!(result, Ok(3));
+	let elapsed = start.elapsed();
+	assert!(elapsed >= DIFF, "{elapsed:?}");
+	assert!(elapsed <= MAX_DIFF);
+
+	// Returns the last error.
+	attempts.set(0);
+	let backoff = Backoff::exponential(Duration::ZERO).with_max_retries(1);
+	assert_eq!(web::retry(op, backoff).await, Err(2));
+	assert_eq!(attempts.get(), 2);
+}
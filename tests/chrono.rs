@@ -0,0 +1,159 @@
+//! Conversions between [`SystemTime`] and [`chrono`] types.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+#![cfg_attr(
+	not(target_arch = "wasm32"),
+	expect(
+		clippy::unnecessary_fallible_conversions,
+		reason = "conversions to `SystemTime` are only fallible on Web"
+	)
+)]
+
+mod util;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta, TimeZone, Utc};
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Duration, SystemTime};
+
+/// 1994-11-06T08:49:37Z.
+const DATE: Duration = Duration::from_secs(784_111_777);
+
+/// [`SystemTime`] to [`DateTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn to_date_time() {
+	let time = SystemTime::UNIX_EPOCH;
+	assert_eq!(DateTime::<Utc>::from(time), DateTime::<Utc>::UNIX_EPOCH);
+
+	let time = SystemTime::UNIX_EPOCH + DATE + Duration::from_nanos(123_456_789);
+	let date_time = DateTime::<Utc>::from(time);
+	let expected = NaiveDate::from_ymd_opt(1994, 11, 6)
+		.unwrap()
+		.and_hms_nano_opt(8, 49, 37, 123_456_789)
+		.unwrap()
+		.and_utc();
+	assert_eq!(date_time, expected);
+	assert_eq!(date_time.naive_utc(), expected.naive_utc());
+}
+
+/// [`DateTime`] to [`SystemTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn from_date_time() {
+	let date_time = Utc.with_ymd_and_hms(1994, 11, 6, 8, 49, 37).unwrap();
+	assert_eq!(
+		SystemTime::try_from(date_time).unwrap(),
+		SystemTime::UNIX_EPOCH + DATE
+	);
+
+	let date_time = FixedOffset::east_opt(3600)
+		.unwrap()
+		.with_ymd_and_hms(1994, 11, 6, 9, 49, 37)
+		.unwrap();
+	assert_eq!(
+		SystemTime::try_from(date_time).unwrap(),
+		SystemTime::UNIX_EPOCH + DATE
+	);
+
+	let naive = NaiveDate::from_ymd_opt(1994, 11, 6)
+		.unwrap()
+		.and_hms_opt(8, 49, 37)
+		.unwrap();
+	assert_eq!(
+		SystemTime::try_from(naive.and_utc()).unwrap(),
+		SystemTime::UNIX_EPOCH + DATE
+	);
+}
+
+/// Sub-second precision round-trips.
+#[wasm_bindgen_test(unsupported = test)]
+fn round_trip() {
+	for nanos in [0, 1, 999_999_999] {
+		let time = SystemTime::UNIX_EPOCH + DATE + Duration::from_nanos(nanos);
+		assert_eq!(
+			SystemTime::try_from(DateTime::<Utc>::from(time)).unwrap(),
+			time
+		);
+		assert_eq!(
+			DateTime::<Utc>::from(time).timestamp_subsec_nanos(),
+			u32::try_from(nanos).unwrap()
+		);
+	}
+}
+
+/// Leap seconds are folded into the following second.
+#[wasm_bindgen_test(unsupported = test)]
+fn leap_second() {
+	let date_time = NaiveDate::from_ymd_opt(2016, 12, 31)
+		.unwrap()
+		.and_hms_milli_opt(23, 59, 59, 1500)
+		.unwrap()
+		.and_utc();
+	assert_eq!(
+		SystemTime::try_from(date_time).unwrap(),
+		SystemTime::UNIX_EPOCH + Duration::from_millis(1_483_228_800_500)
+	);
+}
+
+/// [`NaiveDateTime`](chrono::NaiveDateTime) conversions in UTC.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn naive_date_time() {
+	use chrono::NaiveDateTime;
+
+	let time = SystemTime::UNIX_EPOCH + DATE + Duration::from_nanos(123_456_789);
+	let naive = NaiveDate::from_ymd_opt(1994, 11, 6)
+		.unwrap()
+		.and_hms_nano_opt(8, 49, 37, 123_456_789)
+		.unwrap();
+	assert_eq!(NaiveDateTime::from(time), naive);
+	assert_eq!(SystemTime::try_from(naive), Ok(time));
+	assert_eq!(
+		NaiveDateTime::from(SystemTime::UNIX_EPOCH),
+		DateTime::<Utc>::UNIX_EPOCH.naive_utc()
+	);
+}
+
+/// Times before the Unix epoch.
+#[wasm_bindgen_test(unsupported = test)]
+fn before_epoch() {
+	let date_time = DateTime::<Utc>::UNIX_EPOCH - TimeDelta::milliseconds(1500);
+	assert_eq!(date_time.timestamp(), -2);
+	assert_eq!(date_time.timestamp_subsec_nanos(), 500_000_000);
+
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		let time = SystemTime::UNIX_EPOCH - Duration::from_millis(1500);
+		assert_eq!(DateTime::<Utc>::from(time), date_time);
+		assert_eq!(SystemTime::try_from(date_time), Ok(time));
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	{
+		use web_time::OutOfRangeError;
+
+		assert_eq!(SystemTime::try_from(date_time), Err(OutOfRangeError));
+		assert_eq!(
+			SystemTime::try_from(date_time.naive_utc()),
+			Err(OutOfRangeError)
+		);
+	}
+}
+
+/// [`Duration`] conversions with [`TimeDelta`].
+#[wasm_bindgen_test(unsupported = test)]
+fn duration() {
+	let duration = Duration::new(5, 123_456_789);
+	let delta = TimeDelta::from_std(duration).unwrap();
+	assert_eq!(delta.num_nanoseconds(), Some(5_123_456_789));
+	assert_eq!(delta.to_std(), Ok(duration));
+	TimeDelta::from_std(Duration::MAX).unwrap_err();
+	(-delta).to_std().unwrap_err();
+
+	let earlier = SystemTime::UNIX_EPOCH + DATE;
+	let later = earlier + duration;
+	assert_eq!(
+		DateTime::<Utc>::from(later) - DateTime::<Utc>::from(earlier),
+		delta
+	);
+}
@@ -0,0 +1,56 @@
+//! Tests for [`CoarseClock`].
+
+#![cfg(test)]
+#![no_main]
+
+mod util;
+
+use static_assertions::assert_impl_all;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::CoarseClock;
+use web_time::{Clock, Instant};
+
+use self::util::{sleep, DIFF, WAIT};
+
+/// [`CoarseClock`] can be sent to other threads.
+#[wasm_bindgen_test]
+const fn traits() {
+	assert_impl_all!(CoarseClock: Clock, Copy, Send, Sync);
+}
+
+/// [`CoarseClock`] returns the same [`Instant`] for the rest of the task.
+#[wasm_bindgen_test]
+fn cached() {
+	let first = CoarseClock.now();
+	let start = Instant::now();
+	assert!(first <= start);
+
+	while Instant::now() - start < DIFF {}
+
+	assert_eq!(CoarseClock.now(), first);
+	assert!(CoarseClock.now() <= Instant::now());
+}
+
+/// [`CoarseClock::refresh()`] samples a new [`Instant`].
+#[wasm_bindgen_test]
+fn refresh() {
+	let first = CoarseClock.now();
+
+	while Instant::now() - first < DIFF {}
+
+	let refreshed = CoarseClock::refresh();
+	assert!(refreshed - first >= DIFF);
+	assert_eq!(CoarseClock.now(), refreshed);
+	assert!(CoarseClock.now() <= Instant::now());
+}
+
+/// [`CoarseClock`] samples a new [`Instant`] in the next task.
+#[wasm_bindgen_test]
+async fn next_task() {
+	let first = CoarseClock.now();
+	sleep(WAIT).await;
+
+	let next = CoarseClock.now();
+	assert!(next - first >= WAIT);
+	assert_eq!(CoarseClock.now(), next);
+}
@@ -0,0 +1,97 @@
+//! [`Deadline`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+use static_assertions::assert_impl_all;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Deadline, Duration, Instant};
+
+use self::util::{sleep, DIFF, WAIT};
+
+/// [`Deadline`] implements common traits.
+#[wasm_bindgen_test(unsupported = test)]
+const fn traits() {
+	assert_impl_all!(Deadline: Clone, Copy, Eq, From<Instant>, Send, Sync);
+}
+
+/// [`Deadline::from_timeout()`] expires after the timeout.
+#[wasm_bindgen_test(unsupported = pollster::test)]
+async fn from_timeout() {
+	let deadline = Deadline::from_timeout(WAIT);
+	assert!(!deadline.is_expired());
+	assert!(deadline.remaining() <= WAIT);
+	assert!(deadline.remaining() >= DIFF);
+
+	sleep(WAIT).await;
+	assert!(deadline.is_expired());
+	assert_eq!(deadline.remaining(), Duration::ZERO);
+
+	let deadline = Deadline::from_timeout(Duration::ZERO);
+	assert!(deadline.is_expired());
+	assert!(deadline.instant().unwrap() <= Instant::now());
+}
+
+/// [`Deadline::NEVER`] never expires.
+#[wasm_bindgen_test(unsupported = test)]
+fn never() {
+	assert!(!Deadline::NEVER.is_expired());
+	assert_eq!(Deadline::NEVER.remaining(), Duration::MAX);
+	assert_eq!(Deadline::NEVER.instant(), None);
+
+	// Overflowing timeouts never expire.
+	assert_eq!(Deadline::from_timeout(Duration::MAX), Deadline::NEVER);
+}
+
+/// [`Deadline::min()`] returns the earlier [`Deadline`].
+#[wasm_bindgen_test(unsupported = test)]
+fn min() {
+	let now = Instant::now();
+	let earlier = Deadline::at(now);
+	let later = Deadline::from(now + WAIT);
+
+	assert_eq!(earlier.min(later), earlier);
+	assert_eq!(later.min(earlier), earlier);
+	assert_eq!(later.min(Deadline::NEVER), later);
+	assert_eq!(Deadline::NEVER.min(later), later);
+	assert_eq!(Deadline::NEVER.min(Deadline::NEVER), Deadline::NEVER);
+}
+
+/// [`Deadline::checked_add()`] postpones the [`Deadline`].
+#[wasm_bindgen_test(unsupported = test)]
+fn checked_add() {
+	let now = Instant::now();
+	let deadline = Deadline::at(now);
+
+	assert_eq!(deadline.checked_add(WAIT), Some(Deadline::at(now + WAIT)));
+	assert_eq!(deadline.checked_add(Duration::MAX), None);
+	assert_eq!(
+		Deadline::NEVER.checked_add(Duration::MAX),
+		Some(Deadline::NEVER)
+	);
+}
+
+/// De/Serialization of [`Deadline`] as the remaining [`Duration`].
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test(unsupported = test)]
+fn serde() {
+	let deadline = Deadline::from_timeout(WAIT);
+	let serialized = serde_json::to_string(&deadline).unwrap();
+	let deserialized: Deadline = serde_json::from_str(&serialized).unwrap();
+	assert!(deserialized.instant().unwrap() >= deadline.instant().unwrap());
+	assert!(deserialized.remaining() <= WAIT);
+
+	let serialized = serde_json::to_string(&Deadline::NEVER).unwrap();
+	assert_eq!(serialized, "null");
+	let deserialized: Deadline = serde_json::from_str(&serialized).unwrap();
+	assert_eq!(deserialized, Deadline::NEVER);
+
+	// Expired deadlines stay expired.
+	let serialized = serde_json::to_string(&Deadline::at(Instant::now())).unwrap();
+	assert_eq!(serialized, r#"{"secs":0,"nanos":0}"#);
+	let deserialized: Deadline = serde_json::from_str(&serialized).unwrap();
+	assert!(deserialized.is_expired());
+}
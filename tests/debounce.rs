@@ -0,0 +1,337 @@
+//! Tests for debouncing and throttling.
+
+#![cfg(test)]
+#![no_main]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod util;
+
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::{Debounced, Throttled};
+
+use self::util::{sleep, WAIT};
+
+/// Returns a callback recording its calls.
+fn recorder() -> (Rc<RefCell<Vec<u32>>>, impl FnMut(u32)) {
+	let calls = Rc::new(RefCell::new(Vec::new()));
+	let callback = {
+		let calls = Rc::clone(&calls);
+		move |value| calls.borrow_mut().push(value)
+	};
+
+	(calls, callback)
+}
+
+/// [`Debounced`] only passes on the last call.
+#[wasm_bindgen_test]
+async fn debounced() {
+	let (calls, callback) = recorder();
+	let debounced = Debounced::new(WAIT, callback);
+
+	debounced.call(1);
+	debounced.call(2);
+	assert!(debounced.is_pending());
+
+	sleep(WAIT / 2).await;
+	debounced.call(3);
+	sleep(WAIT / 2).await;
+	assert!(calls.borrow().is_empty());
+
+	sleep(WAIT).await;
+	assert_eq!(*calls.borrow(), [3]);
+	assert!(!debounced.is_pending());
+}
+
+/// [`Debounced::with_leading()`] passes on the first call.
+#[wasm_bindgen_test]
+async fn debounced_leading() {
+	let (calls, callback) = recorder();
+	let debounced = Debounced::new(WAIT, callback)
+		.with_leading(true)
+		.with_trailing(false);
+
+	debounced.call(1);
+	debounced.call(2);
+	assert_eq!(*calls.borrow(), [1]);
+	assert!(!debounced.is_pending());
+
+	sleep(WAIT * 2).await;
+	debounced.call(3);
+	assert_eq!(*calls.borrow(), [1, 3]);
+
+	sleep(WAIT * 2).await;
+	assert_eq!(*calls.borrow(), [1, 3]);
+}
+
+/// [`Throttled`] passes on the first and the last call.
+#[wasm_bindgen_test]
+async fn throttled() {
+	let (calls, callback) = recorder();
+	let throttled = Throttled::new(WAIT, callback);
+
+	throttled.call(1);
+	throttled.call(2);
+	throttled.call(3);
+	assert_eq!(*calls.borrow(), [1]);
+
+	sleep(WAIT * 2).await;
+	assert_eq!(*calls.borrow(), [1, 3]);
+
+	// The window restarted after the trailing call.
+	sleep(WAIT * 2).await;
+	throttled.call(4);
+	assert_eq!(*calls.borrow(), [1, 3, 4]);
+}
+
+/// [`Throttled::with_leading()`] disabled holds back the first call.
+#[wasm_bindgen_test]
+async fn throttled_trailing() {
+	let (calls, callback) = recorder();
+	let throttled = Throttled::new(WAIT, callback).with_leading(false);
+
+	throttled.call(1);
+	throttled.call(2);
+	assert!(calls.borrow().is_empty());
+
+	sleep(WAIT * 2).await;
+	assert_eq!(*calls.borrow(), [2]);
+}
+
+/// [`Debounced::flush()`] and [`Debounced::cancel()`].
+#[wasm_bindgen_test]
+async fn flush_cancel() {
+	let (calls, callback) = recorder();
+	let debounced = Debounced::new(WAIT, callback);
+
+	debounced.call(1);
+	debounced.flush();
+	assert_eq!(*calls.borrow(), [1]);
+
+	debounced.call(2);
+	debounced.cancel();
+	assert!(!debounced.is_pending());
+
+	sleep(WAIT * 2).await;
+	assert_eq!(*calls.borrow(), [1]);
+}
+
+/// Dropping the last clone discards the pending call.
+#[wasm_bindgen_test]
+async fn discard() {
+	let (calls, callback) = recorder();
+	let debounced = Debounced::new(WAIT, callback);
+	let clone = debounced.clone();
+
+	debounced.call(1);
+	drop(debounced);
+	assert!(clone.is_pending());
+
+	drop(clone);
+	sleep(WAIT * 2).await;
+	assert!(calls.borrow().is_empty());
+}
+
+/// Calls from within the callback, including ones passed on right away.
+#[wasm_bindgen_test]
+async fn reentrant() {
+	for leading in [false, true] {
+		let calls = Rc::new(RefCell::new(Vec::new()));
+		let debounced = Rc::new(RefCell::new(None::<Debounced<u32>>));
+		let callback = {
+			let calls = Rc::clone(&calls);
+			let debounced = Rc::clone(&debounced);
+			move |value| {
+				calls.borrow_mut().push(value);
+
+				if value < 3 {
+					debounced.borrow().as_ref().unwrap().call(value + 1);
+				}
+			}
+		};
+		*debounced.borrow_mut() = Some(Debounced::new(WAIT, callback).with_leading(leading));
+
+		debounced.borrow().as_ref().unwrap().call(1);
+		sleep(WAIT * 5).await;
+		assert_eq!(*calls.borrow(), [1, 2, 3], "leading: {leading}");
+
+		// Break the cycle.
+		debounced.borrow_mut().take();
+	}
+}
+
+/// [`debounce()`](web_time::web::debounce) and
+/// [`throttle()`](web_time::web::throttle).
+#[cfg(feature = "futures")]
+mod stream {
+	use alloc::collections::VecDeque;
+	use core::future::poll_fn;
+	use core::pin::Pin;
+	use core::task::{Context, Poll, Waker};
+
+	use futures_core::{FusedStream, Stream};
+	use wasm_bindgen_test::wasm_bindgen_test;
+	use web_time::web::{self, Debounce, Throttle};
+	use web_time::Instant;
+
+	use super::*;
+	use crate::util::{DIFF, MAX_DIFF};
+
+	/// Manually fed [`Stream`].
+	#[derive(Clone, Default)]
+	struct Source(Rc<RefCell<Channel>>);
+
+	/// State of a [`Source`].
+	#[derive(Default)]
+	struct Channel {
+		/// Items not yet received.
+		items: VecDeque<u32>,
+		/// If no more items will be sent.
+		closed: bool,
+		/// Waker of the receiving task.
+		waker: Option<Waker>,
+	}
+
+	impl Source {
+		/// Sends all `items`.
+		fn send(&self, items: &[u32]) {
+			let mut channel = self.0.borrow_mut();
+			channel.items.extend(items);
+
+			if let Some(waker) = channel.waker.take() {
+				waker.wake();
+			}
+		}
+
+		/// Ends the [`Stream`] after all sent items.
+		fn close(&self) {
+			let mut channel = self.0.borrow_mut();
+			channel.closed = true;
+
+			if let Some(waker) = channel.waker.take() {
+				waker.wake();
+			}
+		}
+	}
+
+	impl Stream for Source {
+		type Item = u32;
+
+		fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+			let mut channel = self.0.borrow_mut();
+
+			if let Some(item) = channel.items.pop_front() {
+				Poll::Ready(Some(item))
+			} else if channel.closed {
+				Poll::Ready(None)
+			} else {
+				channel.waker = Some(cx.waker().clone());
+				Poll::Pending
+			}
+		}
+	}
+
+	/// Returns the next item of `stream`.
+	async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+		poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+	}
+
+	/// Polls `stream` once, receiving all sent items, and asserts that it
+	/// holds them back.
+	async fn assert_pending<S: Stream + Unpin>(stream: &mut S) {
+		assert!(
+			poll_fn(|cx| Poll::Ready(Pin::new(&mut *stream).poll_next(cx)))
+				.await
+				.is_pending()
+		);
+	}
+
+	/// [`debounce()`](web::debounce) yields the last item.
+	#[wasm_bindgen_test]
+	async fn debounce() {
+		let source = Source::default();
+		let mut stream: Debounce<_> = web::debounce(source.clone(), WAIT);
+
+		source.send(&[1, 2, 3]);
+		let start = Instant::now();
+		assert_eq!(next(&mut stream).await, Some(3));
+		let elapsed = start.elapsed();
+		assert!(elapsed >= DIFF, "{elapsed:?}");
+		assert!(elapsed <= MAX_DIFF);
+
+		// Ending flushes the pending item.
+		source.send(&[4]);
+		source.close();
+		let start = Instant::now();
+		assert_eq!(next(&mut stream).await, Some(4));
+		assert!(start.elapsed() < DIFF);
+		assert_eq!(next(&mut stream).await, None);
+		assert!(stream.is_terminated());
+	}
+
+	/// [`Debounce::with_leading()`] yields the first item right away.
+	#[wasm_bindgen_test]
+	async fn debounce_leading() {
+		let source = Source::default();
+		let mut stream = web::debounce(source.clone(), WAIT)
+			.with_leading(true)
+			.with_trailing(false);
+
+		source.send(&[1, 2, 3]);
+		let start = Instant::now();
+		assert_eq!(next(&mut stream).await, Some(1));
+		assert!(start.elapsed() < DIFF);
+		assert_pending(&mut stream).await;
+
+		sleep(WAIT * 2).await;
+		source.send(&[4]);
+		assert_eq!(next(&mut stream).await, Some(4));
+
+		source.close();
+		assert_eq!(next(&mut stream).await, None);
+	}
+
+	/// [`throttle()`](web::throttle) yields the first and the last item.
+	#[wasm_bindgen_test]
+	async fn throttle() {
+		let source = Source::default();
+		let mut stream: Throttle<_> = web::throttle(source.clone(), WAIT);
+
+		source.send(&[1, 2, 3]);
+		let start = Instant::now();
+		assert_eq!(next(&mut stream).await, Some(1));
+		assert!(start.elapsed() < DIFF);
+
+		assert_eq!(next(&mut stream).await, Some(3));
+		let elapsed = start.elapsed();
+		assert!(elapsed >= DIFF, "{elapsed:?}");
+		assert!(elapsed <= MAX_DIFF);
+
+		source.close();
+		assert_eq!(next(&mut stream).await, None);
+		assert!(stream.is_terminated());
+		let _source: Source = stream.into_inner();
+	}
+
+	/// [`Throttle::with_trailing()`] disabled drops held back items.
+	#[wasm_bindgen_test]
+	async fn throttle_leading() {
+		let source = Source::default();
+		let mut stream = web::throttle(source.clone(), WAIT).with_trailing(false);
+
+		source.send(&[1, 2, 3]);
+		assert_eq!(next(&mut stream).await, Some(1));
+		assert_pending(&mut stream).await;
+
+		sleep(WAIT * 2).await;
+		source.send(&[4, 5]);
+		source.close();
+		assert_eq!(next(&mut stream).await, Some(4));
+		assert_eq!(next(&mut stream).await, None);
+	}
+}
@@ -0,0 +1,259 @@
+//! [`Histogram`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+use static_assertions::assert_impl_all;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Duration, Histogram, HistogramSnapshot};
+
+use self::util::ManualClock;
+
+/// Checks that `value` is `expected` within the relative error of
+/// [`Histogram`].
+#[track_caller]
+fn assert_close(value: Option<Duration>, expected: Duration) {
+	let value = value.unwrap();
+	assert!(value >= expected, "{value:?} < {expected:?}");
+	assert!(
+		value <= expected + expected / 32,
+		"{value:?} > {expected:?}"
+	);
+}
+
+/// [`Histogram`] can be shared between threads.
+#[wasm_bindgen_test(unsupported = test)]
+const fn traits() {
+	assert_impl_all!(Histogram: Send, Sync);
+	assert_impl_all!(HistogramSnapshot: Clone, Send, Sync);
+}
+
+/// An empty [`Histogram`].
+#[wasm_bindgen_test(unsupported = test)]
+fn empty() {
+	let snapshot = Histogram::new().snapshot();
+	assert!(snapshot.is_empty());
+	assert_eq!(snapshot.count(), 0);
+	assert_eq!(snapshot.min(), None);
+	assert_eq!(snapshot.max(), None);
+	assert_eq!(snapshot.p50(), None);
+	assert_eq!(snapshot, HistogramSnapshot::new());
+}
+
+/// A single value is reported exactly.
+#[wasm_bindgen_test(unsupported = test)]
+fn exact() {
+	for nanos in [0, 1, 31, 32, 63, 64, 65, 1_000, 123_456_789, u64::MAX] {
+		let histogram = Histogram::new();
+		let duration = Duration::from_nanos(nanos);
+		histogram.record(duration);
+
+		let snapshot = histogram.snapshot();
+		assert_eq!(snapshot.count(), 1);
+		assert_eq!(snapshot.min(), Some(duration));
+		assert_eq!(snapshot.max(), Some(duration));
+		assert_eq!(snapshot.quantile(0.), Some(duration));
+		assert_eq!(snapshot.p50(), Some(duration));
+		assert_eq!(snapshot.quantile(1.), Some(duration));
+	}
+
+	// Saturates.
+	let histogram = Histogram::new();
+	histogram.record(Duration::MAX);
+	assert_eq!(
+		histogram.snapshot().max(),
+		Some(Duration::from_nanos(u64::MAX))
+	);
+}
+
+/// Percentiles are within the relative error.
+#[wasm_bindgen_test(unsupported = test)]
+fn percentiles() {
+	let histogram = Histogram::new();
+
+	for millis in (1..=1000).rev() {
+		histogram.record(Duration::from_millis(millis));
+	}
+
+	let snapshot = histogram.snapshot();
+	assert_eq!(snapshot.count(), 1000);
+	assert_eq!(snapshot.min(), Some(Duration::from_millis(1)));
+	assert_eq!(snapshot.max(), Some(Duration::from_secs(1)));
+	assert_close(snapshot.quantile(0.), Duration::from_millis(1));
+	assert_close(snapshot.p50(), Duration::from_millis(500));
+	assert_close(snapshot.p90(), Duration::from_millis(900));
+	assert_close(snapshot.p99(), Duration::from_millis(990));
+	assert_eq!(snapshot.quantile(1.), Some(Duration::from_secs(1)));
+
+	// Small values are exact.
+	let histogram = Histogram::new();
+
+	for nanos in 0..64 {
+		histogram.record(Duration::from_nanos(nanos));
+	}
+
+	let snapshot = histogram.snapshot();
+	assert_eq!(snapshot.p50(), Some(Duration::from_nanos(31)));
+	assert_eq!(snapshot.quantile(0.75), Some(Duration::from_nanos(47)));
+}
+
+/// [`Histogram::record_since()`] and [`Histogram::time()`].
+#[wasm_bindgen_test(unsupported = test)]
+fn clock() {
+	let clock = ManualClock::new();
+	let histogram = Histogram::with_clock(&clock);
+
+	clock.advance(Duration::from_millis(10));
+	assert_eq!(
+		histogram.record_since(clock.start()),
+		Duration::from_millis(10)
+	);
+
+	let output = histogram.time(|| {
+		clock.advance(Duration::from_millis(20));
+		42
+	});
+	assert_eq!(output, 42);
+
+	// Saturates if `start` is in the future.
+	histogram.record_since(clock.now() + Duration::from_secs(1));
+
+	let snapshot = histogram.snapshot();
+	assert_eq!(snapshot.count(), 3);
+	assert_eq!(snapshot.min(), Some(Duration::ZERO));
+	assert_eq!(snapshot.max(), Some(Duration::from_millis(20)));
+	assert_close(snapshot.p50(), Duration::from_millis(10));
+}
+
+/// [`HistogramSnapshot::merge()`] and [`Histogram::reset()`].
+#[wasm_bindgen_test(unsupported = test)]
+fn merge() {
+	let histogram = Histogram::new();
+	histogram.record(Duration::from_millis(1));
+	histogram.record(Duration::from_millis(2));
+	let mut merged = histogram.snapshot();
+
+	histogram.reset();
+	assert!(histogram.snapshot().is_empty());
+
+	histogram.record(Duration::from_millis(3));
+	histogram.record(Duration::from_millis(4));
+	merged.merge(&histogram.snapshot());
+
+	histogram.record(Duration::from_millis(1));
+	histogram.record(Duration::from_millis(2));
+
+	assert_eq!(merged, histogram.snapshot());
+	assert_eq!(merged.count(), 4);
+	assert_eq!(merged.min(), Some(Duration::from_millis(1)));
+	assert_eq!(merged.max(), Some(Duration::from_millis(4)));
+
+	let mut empty = HistogramSnapshot::new();
+	empty.merge(&HistogramSnapshot::new());
+	assert!(empty.is_empty());
+	empty.merge(&merged);
+	assert_eq!(empty, merged);
+}
+
+/// Recording from multiple threads.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn threads() {
+	use std::sync::Arc;
+	use std::thread;
+
+	let histogram = Arc::new(Histogram::new());
+
+	let handles: Vec<_> = (0..4)
+		.map(|_| {
+			let histogram = Arc::clone(&histogram);
+			thread::spawn(move || {
+				for millis in 1..=1000 {
+					histogram.record(Duration::from_millis(millis));
+				}
+			})
+		})
+		.collect();
+
+	for handle in handles {
+		handle.join().unwrap();
+	}
+
+	let snapshot = histogram.snapshot();
+	assert_eq!(snapshot.count(), 4000);
+	assert_close(snapshot.p50(), Duration::from_millis(500));
+}
+
+/// Snapshots stay consistent while other threads record and reset.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn threads_reset() {
+	use std::sync::atomic::{AtomicBool, Ordering};
+	use std::sync::Arc;
+	use std::thread;
+
+	let histogram = Arc::new(Histogram::new());
+	let done = Arc::new(AtomicBool::new(false));
+
+	let handles: Vec<_> = (0..2)
+		.map(|_| {
+			let histogram = Arc::clone(&histogram);
+			let done = Arc::clone(&done);
+			thread::spawn(move || {
+				while !done.load(Ordering::Relaxed) {
+					histogram.record(Duration::from_millis(1));
+					histogram.reset();
+				}
+			})
+		})
+		.collect();
+
+	for _ in 0..10_000 {
+		let snapshot = histogram.snapshot();
+
+		if let (Some(min), Some(max)) = (snapshot.min(), snapshot.max()) {
+			assert!(min <= max, "{min:?} > {max:?}");
+			assert_close(snapshot.p50(), Duration::from_millis(1));
+		}
+	}
+
+	done.store(true, Ordering::Relaxed);
+
+	for handle in handles {
+		handle.join().unwrap();
+	}
+}
+
+/// De/Serialization of [`HistogramSnapshot`].
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test(unsupported = test)]
+fn serde() {
+	let histogram = Histogram::new();
+	histogram.record(Duration::from_millis(1));
+	histogram.record(Duration::from_millis(1));
+	histogram.record(Duration::from_secs(1));
+	let snapshot = histogram.snapshot();
+
+	let serialized = serde_json::to_string(&snapshot).unwrap();
+	let deserialized: HistogramSnapshot = serde_json::from_str(&serialized).unwrap();
+	assert_eq!(snapshot, deserialized);
+
+	let serialized = serde_json::to_string(&HistogramSnapshot::new()).unwrap();
+	assert_eq!(serialized, r#"{"min":null,"max":null,"buckets":[]}"#);
+	let deserialized: HistogramSnapshot = serde_json::from_str(&serialized).unwrap();
+	assert!(deserialized.is_empty());
+
+	// Invalid bucket index.
+	serde_json::from_str::<HistogramSnapshot>(
+		r#"{"min":{"secs":0,"nanos":1},"max":{"secs":0,"nanos":1},"buckets":[[1920,1]]}"#,
+	)
+	.unwrap_err();
+	// Extremes without values.
+	serde_json::from_str::<HistogramSnapshot>(
+		r#"{"min":{"secs":0,"nanos":1},"max":{"secs":0,"nanos":1},"buckets":[]}"#,
+	)
+	.unwrap_err();
+}
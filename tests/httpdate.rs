@@ -0,0 +1,212 @@
+//! [`httpdate`](web_time::httpdate) tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+extern crate alloc;
+
+mod util;
+
+use alloc::format;
+use alloc::string::ToString;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::httpdate::{HttpDate, HttpDateError, HttpDateExt};
+use web_time::{Duration, SystemTime, UtcDateTime};
+
+/// 1994-11-06T08:49:37Z.
+const DATE: Duration = Duration::from_secs(784_111_777);
+
+/// Full day names, starting with Monday.
+const FULL_DAYS: [&str; 7] = [
+	"Monday",
+	"Tuesday",
+	"Wednesday",
+	"Thursday",
+	"Friday",
+	"Saturday",
+	"Sunday",
+];
+
+/// Formatting as IMF-fixdate.
+#[wasm_bindgen_test(unsupported = test)]
+fn format() {
+	for (secs, expected) in [
+		(0, "Thu, 01 Jan 1970 00:00:00 GMT"),
+		(784_111_777, "Sun, 06 Nov 1994 08:49:37 GMT"),
+		(951_782_400, "Tue, 29 Feb 2000 00:00:00 GMT"),
+		(1_709_251_199, "Thu, 29 Feb 2024 23:59:59 GMT"),
+		(253_402_300_799, "Fri, 31 Dec 9999 23:59:59 GMT"),
+	] {
+		let time = SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+		assert_eq!(time.to_http_date().unwrap().to_string(), expected);
+	}
+}
+
+/// Sub-second precision is truncated.
+#[wasm_bindgen_test(unsupported = test)]
+fn truncate() {
+	let time = SystemTime::UNIX_EPOCH + DATE + Duration::from_millis(999);
+	let date = time.to_http_date().unwrap();
+	assert_eq!(SystemTime::from(date), SystemTime::UNIX_EPOCH + DATE);
+}
+
+/// Formatting out of range.
+#[wasm_bindgen_test(unsupported = test)]
+fn format_out_of_range() {
+	let last = SystemTime::UNIX_EPOCH + Duration::from_secs(253_402_300_799);
+	let time = last + Duration::from_secs(1);
+	assert_eq!(time.to_http_date(), Err(HttpDateError));
+
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		let time = SystemTime::UNIX_EPOCH - Duration::from_secs(1);
+		assert_eq!(time.to_http_date(), Err(HttpDateError));
+	}
+}
+
+/// Parsing all supported formats.
+#[wasm_bindgen_test(unsupported = test)]
+fn parse() {
+	for date in [
+		"Sun, 06 Nov 1994 08:49:37 GMT",
+		"Sunday, 06-Nov-94 08:49:37 GMT",
+		"Sun Nov  6 08:49:37 1994",
+		"Sun Nov 06 08:49:37 1994",
+	] {
+		assert_eq!(
+			SystemTime::from_http_date(date),
+			Ok(SystemTime::UNIX_EPOCH + DATE),
+			"{date}"
+		);
+	}
+}
+
+/// Two-digit years of RFC 850 are interpreted relative to the current year.
+#[wasm_bindgen_test(unsupported = test)]
+fn parse_rfc850_year() {
+	let now = UtcDateTime::from(SystemTime::now()).year();
+
+	for (offset, expected) in [(-1, -1), (0, 0), (50, 50), (51, -49), (99, -1)] {
+		let year = now + offset;
+		let expected = UtcDateTime::new(now + expected, 1, 1, 0, 0, 0, 0).unwrap();
+		let weekday = FULL_DAYS
+			.get(usize::from(expected.weekday().number_days_from_monday()))
+			.unwrap();
+		let date = format!("{weekday}, 01-Jan-{:02} 00:00:00 GMT", year % 100);
+
+		assert_eq!(
+			SystemTime::from_http_date(&date),
+			Ok(expected.to_system_time().unwrap()),
+			"{date}"
+		);
+	}
+}
+
+/// Two-digit years of RFC 850 relative to a given year.
+#[wasm_bindgen_test(unsupported = test)]
+fn parse_with_year() {
+	for (date, expected) in [
+		(
+			"Sunday, 06-Nov-94 08:49:37 GMT",
+			"Sun, 06 Nov 1994 08:49:37 GMT",
+		),
+		(
+			"Wednesday, 01-Jan-76 00:00:00 GMT",
+			"Wed, 01 Jan 2076 00:00:00 GMT",
+		),
+		(
+			"Saturday, 01-Jan-77 00:00:00 GMT",
+			"Sat, 01 Jan 1977 00:00:00 GMT",
+		),
+		(
+			"Thursday, 01-Jan-26 00:00:00 GMT",
+			"Thu, 01 Jan 2026 00:00:00 GMT",
+		),
+		// Other formats ignore the given year.
+		(
+			"Sun, 06 Nov 1994 08:49:37 GMT",
+			"Sun, 06 Nov 1994 08:49:37 GMT",
+		),
+	] {
+		let date = HttpDate::parse_with_year(date, 2026).unwrap();
+		assert_eq!(date.to_string(), expected);
+	}
+
+	// Before 1970.
+	assert_eq!(
+		HttpDate::parse_with_year("Saturday, 01-Jan-27 00:00:00 GMT", 1976),
+		Err(HttpDateError)
+	);
+}
+
+/// Leap seconds are folded into the following second.
+#[wasm_bindgen_test(unsupported = test)]
+fn parse_leap_second() {
+	let date: HttpDate = "Sat, 31 Dec 2016 23:59:60 GMT".parse().unwrap();
+	assert_eq!(date.to_string(), "Sun, 01 Jan 2017 00:00:00 GMT");
+}
+
+/// Invalid dates.
+#[wasm_bindgen_test(unsupported = test)]
+fn parse_invalid() {
+	for date in [
+		"",
+		"Sun, 06 Nov 1994 08:49:37",
+		"Sun, 06 Nov 1994 08:49:37 UTC",
+		"sun, 06 Nov 1994 08:49:37 GMT",
+		"Sun, 06 nov 1994 08:49:37 GMT",
+		"Mon, 06 Nov 1994 08:49:37 GMT",
+		"Sun, 6 Nov 1994 08:49:37 GMT",
+		"Sun, 00 Nov 1994 08:49:37 GMT",
+		"Thu, 31 Nov 1994 08:49:37 GMT",
+		"Thu, 29 Feb 2023 00:00:00 GMT",
+		"Sun, 06 Nov 1994 24:00:00 GMT",
+		"Sun, 06 Nov 1994 08:60:00 GMT",
+		"Sun, 06 Nov 1994 08:49:61 GMT",
+		"Sun, 06 Nov 1994 08:49:3a GMT",
+		"Wed, 31 Dec 1969 23:59:59 GMT",
+		"Sun, 06 Nov 1994 08:49:37 GMT ",
+		"Sun, 06-Nov-94 08:49:37 GMT",
+		"Sunday, 06 Nov 1994 08:49:37 GMT",
+		"Sunday, 06-Nov-1994 08:49:37 GMT",
+		"Sun Nov 6 08:49:37 1994",
+		"Sun Nov  6 08:49:37 94",
+	] {
+		assert_eq!(
+			SystemTime::from_http_date(date),
+			Err(HttpDateError),
+			"{date}"
+		);
+	}
+}
+
+/// Formatting and parsing round-trips.
+#[wasm_bindgen_test(unsupported = test)]
+fn round_trip() {
+	// Roughly every 13 days and 7 hours across the whole range.
+	for secs in (0..253_402_300_800).step_by(1_148_423) {
+		let time = SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+		let date = time.to_http_date().unwrap();
+		assert_eq!(
+			SystemTime::from_http_date(&date.to_string()),
+			Ok(time),
+			"{date}"
+		);
+	}
+}
+
+/// Interoperability with [`std::time::SystemTime`].
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
+#[wasm_bindgen_test]
+fn std() {
+	use std::time::SystemTime as StdSystemTime;
+
+	let time = StdSystemTime::from_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+	assert_eq!(time, StdSystemTime::UNIX_EPOCH + DATE);
+	assert_eq!(
+		time.to_http_date().unwrap().to_string(),
+		"Sun, 06 Nov 1994 08:49:37 GMT"
+	);
+}
@@ -0,0 +1,67 @@
+//! Tests for [`idle()`].
+
+#![cfg(test)]
+#![no_main]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod util;
+
+use core::future::{poll_fn, Future};
+use core::pin::Pin;
+use core::task::Poll;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::{self, IdleBudget};
+use web_time::{Duration, Instant};
+
+use self::util::{sleep, WAIT};
+
+/// Longest budget handed out.
+const MAX_BUDGET: Duration = Duration::from_millis(50);
+
+/// Checks that `budget` is consistent with [`Instant::now()`].
+fn check(start: Instant, budget: IdleBudget) {
+	let now = Instant::now();
+	assert!(budget.deadline() >= start);
+	assert!(budget.deadline() <= now + MAX_BUDGET);
+	assert!(budget.remaining() <= budget.deadline().saturating_duration_since(now));
+}
+
+/// [`web::idle()`] returns a budget relative to [`Instant::now()`].
+#[wasm_bindgen_test]
+async fn idle() {
+	let start = Instant::now();
+	let budget = web::idle().await;
+	check(start, budget);
+	assert!(!budget.did_timeout());
+
+	// Using up the budget.
+	while budget.remaining() > Duration::ZERO {}
+	assert!(Instant::now() >= budget.deadline());
+}
+
+/// [`web::idle_with_timeout()`].
+#[wasm_bindgen_test]
+async fn timeout() {
+	let start = Instant::now();
+	let budget = web::idle_with_timeout(WAIT).await;
+	check(start, budget);
+
+	if budget.did_timeout() {
+		assert!(start.elapsed() >= WAIT);
+	}
+}
+
+/// Dropping [`web::Idle`] before completion cancels the callback.
+#[wasm_bindgen_test]
+async fn cancel() {
+	let mut idle = web::idle();
+	assert!(poll_fn(|cx| Poll::Ready(Pin::new(&mut idle).poll(cx)))
+		.await
+		.is_pending());
+	drop(idle);
+
+	sleep(WAIT).await;
+	let start = Instant::now();
+	check(start, web::idle().await);
+}
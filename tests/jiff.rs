@@ -0,0 +1,121 @@
+//! Conversions between [`SystemTime`] and [`jiff`] types.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+#![cfg_attr(
+	not(target_arch = "wasm32"),
+	expect(
+		clippy::unnecessary_fallible_conversions,
+		reason = "conversions to `SystemTime` are only fallible on Web"
+	)
+)]
+
+mod util;
+
+use jiff::Timestamp;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{web, Duration, SystemTime};
+
+/// 1994-11-06T08:49:37Z.
+const DATE: Duration = Duration::from_secs(784_111_777);
+
+/// [`SystemTime`] to [`Timestamp`].
+#[wasm_bindgen_test(unsupported = test)]
+fn to_timestamp() {
+	assert_eq!(
+		Timestamp::try_from(SystemTime::UNIX_EPOCH).unwrap(),
+		Timestamp::UNIX_EPOCH
+	);
+
+	let time = SystemTime::UNIX_EPOCH + DATE + Duration::from_nanos(123_456_789);
+	assert_eq!(
+		Timestamp::try_from(time).unwrap(),
+		Timestamp::new(784_111_777, 123_456_789).unwrap()
+	);
+}
+
+/// [`Timestamp`] to [`SystemTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn from_timestamp() {
+	assert_eq!(
+		SystemTime::try_from(Timestamp::UNIX_EPOCH).unwrap(),
+		SystemTime::UNIX_EPOCH
+	);
+	assert_eq!(
+		SystemTime::try_from(Timestamp::new(784_111_777, 999_999_999).unwrap()).unwrap(),
+		SystemTime::UNIX_EPOCH + DATE + Duration::from_nanos(999_999_999)
+	);
+}
+
+/// Nanosecond precision round-trips.
+#[wasm_bindgen_test(unsupported = test)]
+fn round_trip() {
+	for nanos in [0, 1, 2, 500_000_000, 999_999_998, 999_999_999] {
+		for secs in [Duration::ZERO, DATE] {
+			let time = SystemTime::UNIX_EPOCH + secs + Duration::from_nanos(nanos);
+			let timestamp = Timestamp::try_from(time).unwrap();
+			assert_eq!(timestamp.subsec_nanosecond(), i32::try_from(nanos).unwrap());
+			assert_eq!(SystemTime::try_from(timestamp).unwrap(), time);
+		}
+	}
+}
+
+/// The largest [`Timestamp`] round-trips, anything after fails.
+#[wasm_bindgen_test(unsupported = test)]
+fn max() {
+	let time = SystemTime::try_from(Timestamp::MAX).unwrap();
+	assert_eq!(Timestamp::try_from(time).unwrap(), Timestamp::MAX);
+	Timestamp::try_from(time + Duration::from_nanos(1)).unwrap_err();
+	Timestamp::try_from(time + Duration::from_secs(1)).unwrap_err();
+}
+
+/// Times before the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn before_epoch() {
+	use jiff::SignedDuration;
+
+	for nanos in [1, 999_999_999, 1_000_000_001] {
+		let time = SystemTime::UNIX_EPOCH - Duration::from_nanos(nanos);
+		let timestamp = Timestamp::try_from(time).unwrap();
+		assert_eq!(
+			timestamp.as_duration(),
+			-SignedDuration::from_nanos(i64::try_from(nanos).unwrap())
+		);
+		assert_eq!(SystemTime::try_from(timestamp).unwrap(), time);
+	}
+
+	let time = SystemTime::try_from(Timestamp::MIN).unwrap();
+	assert_eq!(Timestamp::try_from(time).unwrap(), Timestamp::MIN);
+	Timestamp::try_from(time - Duration::from_nanos(1)).unwrap_err();
+}
+
+/// [`Timestamp`]s before the Unix epoch can't be represented on Web.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn before_epoch() {
+	for nanos in [1, 999_999_999, 1_000_000_001] {
+		let timestamp = Timestamp::UNIX_EPOCH - Duration::from_nanos(nanos);
+		SystemTime::try_from(timestamp).unwrap_err();
+	}
+
+	SystemTime::try_from(Timestamp::MIN).unwrap_err();
+}
+
+/// [`web::zoned_now()`].
+#[wasm_bindgen_test(unsupported = test)]
+fn zoned_now() {
+	let before = Timestamp::try_from(SystemTime::now()).unwrap();
+	let zoned = web::zoned_now();
+	let after = Timestamp::try_from(SystemTime::now()).unwrap();
+
+	assert!(before <= zoned.timestamp());
+	assert!(zoned.timestamp() <= after);
+
+	#[cfg(target_arch = "wasm32")]
+	assert_eq!(
+		Some(zoned.offset().seconds()),
+		web::local_offset_at(SystemTime::try_from(zoned.timestamp()).unwrap())
+	);
+}
@@ -0,0 +1,112 @@
+//! Test local time zone API exported in [`web_time::web`].
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::{self, FormatOptions, Style};
+use web_time::{Duration, SystemTime};
+
+/// 1994-11-06T08:49:37Z.
+const DATE: Duration = Duration::from_secs(784_111_777);
+
+/// [`web::local_offset_at()`] matches `Date.prototype.getTimezoneOffset()`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn offset() {
+	use js_sys::Date;
+	use wasm_bindgen::JsValue;
+
+	for secs in [0, DATE.as_secs(), 1_719_792_000] {
+		let time = SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+		#[expect(clippy::as_conversions, clippy::cast_precision_loss, reason = "test")]
+		let date = Date::new(&JsValue::from_f64(secs as f64 * 1000.));
+		#[expect(
+			clippy::as_conversions,
+			clippy::cast_possible_truncation,
+			reason = "test"
+		)]
+		let expected = (date.get_timezone_offset() * -60.) as i32;
+
+		assert_eq!(web::local_offset_at(time), Some(expected));
+	}
+}
+
+/// Times that can't be represented by `Date`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn offset_out_of_range() {
+	let time = SystemTime::UNIX_EPOCH + Duration::from_secs(8_640_000_000_001);
+	assert_eq!(web::local_offset_at(time), None);
+	assert_eq!(web::format_local(time, &FormatOptions::new()), None);
+}
+
+/// [`web::local_time_zone_name()`] reports a time zone.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn time_zone_name() {
+	let name = web::local_time_zone_name().unwrap();
+	assert!(!name.is_empty());
+}
+
+/// [`web::format_local()`] with explicit locale and time zone.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn format() {
+	let time = SystemTime::UNIX_EPOCH + DATE;
+
+	let options = FormatOptions::new()
+		.locale("en-US")
+		.time_zone("UTC")
+		.date_style(Style::Full);
+	assert_eq!(
+		web::format_local(time, &options).unwrap(),
+		"Sunday, November 6, 1994"
+	);
+
+	let options = FormatOptions::new()
+		.locale("en-US")
+		.time_zone("UTC")
+		.time_style(Style::Medium)
+		.hour12(false);
+	assert_eq!(web::format_local(time, &options).unwrap(), "08:49:37");
+
+	let options = FormatOptions::new()
+		.locale("de-DE")
+		.time_zone("Europe/Berlin")
+		.date_style(Style::Medium)
+		.time_style(Style::Short);
+	assert_eq!(
+		web::format_local(time, &options).unwrap(),
+		"06.11.1994, 09:49"
+	);
+}
+
+/// Invalid [`FormatOptions`] are rejected.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn format_invalid() {
+	let time = SystemTime::UNIX_EPOCH + DATE;
+	let options = FormatOptions::new().time_zone("Invalid/Zone");
+	assert_eq!(web::format_local(time, &options), None);
+	let options = FormatOptions::new().locale("");
+	assert_eq!(web::format_local(time, &options), None);
+}
+
+/// Native returns [`None`] instead of guessing.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn unsupported() {
+	let time = SystemTime::UNIX_EPOCH + DATE;
+	assert_eq!(web::local_offset_at(time), None);
+	assert_eq!(web::local_time_zone_name(), None);
+	let options = FormatOptions::new()
+		.locale("en-US")
+		.time_zone("UTC")
+		.date_style(Style::Short);
+	assert_eq!(web::format_local(time, &options), None);
+}
@@ -0,0 +1,77 @@
+//! Test profiling API exported in [`web_time::web::profiling`].
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::profiling::{self, Span};
+use web_time::Instant;
+
+use self::util::{sleep, WAIT};
+
+/// Returns the number of [`PerformanceEntry`]s with the given name.
+///
+/// [`PerformanceEntry`]: https://developer.mozilla.org/en-US/docs/Web/API/PerformanceEntry
+#[cfg(all(target_arch = "wasm32", feature = "profiling"))]
+fn entries(name: &str) -> u32 {
+	use js_sys::Array;
+	use wasm_bindgen::prelude::wasm_bindgen;
+
+	#[wasm_bindgen]
+	extern "C" {
+		#[wasm_bindgen(js_namespace = performance, js_name = getEntriesByName)]
+		fn get_entries_by_name(name: &str) -> Array;
+	}
+
+	get_entries_by_name(name).length()
+}
+
+/// [`profiling::mark()`].
+#[wasm_bindgen_test(unsupported = test)]
+fn mark() {
+	profiling::mark("web-time-mark");
+
+	#[cfg(all(target_arch = "wasm32", feature = "profiling"))]
+	assert_eq!(entries("web-time-mark"), 1);
+}
+
+/// [`profiling::mark()`] doesn't panic on reserved names.
+#[wasm_bindgen_test(unsupported = test)]
+fn mark_reserved() {
+	profiling::mark("navigationStart");
+}
+
+/// [`profiling::measure()`].
+#[wasm_bindgen_test(unsupported = pollster::test)]
+async fn measure() {
+	let start = Instant::now();
+	sleep(WAIT).await;
+	profiling::measure("web-time-measure", start, Instant::now());
+
+	#[cfg(all(target_arch = "wasm32", feature = "profiling"))]
+	assert_eq!(entries("web-time-measure"), 1);
+}
+
+/// [`profiling::measure()`] doesn't panic with `end` being earlier then
+/// `start`.
+#[wasm_bindgen_test(unsupported = pollster::test)]
+async fn measure_reversed() {
+	let start = Instant::now();
+	sleep(WAIT).await;
+	profiling::measure("web-time-measure-reversed", Instant::now(), start);
+}
+
+/// [`Span`].
+#[wasm_bindgen_test(unsupported = pollster::test)]
+async fn span() {
+	{
+		let _span = Span::new("web-time-span");
+		sleep(WAIT).await;
+	}
+
+	#[cfg(all(target_arch = "wasm32", feature = "profiling"))]
+	assert_eq!(entries("web-time-span"), 1);
+}
@@ -0,0 +1,209 @@
+//! [`RateLimiter`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+use core::num::NonZeroU32;
+
+use static_assertions::assert_impl_all;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Duration, Quota, RateLimiter};
+
+use self::util::ManualClock;
+
+/// Shorthand for [`NonZeroU32::new()`].
+fn non_zero(value: u32) -> NonZeroU32 {
+	NonZeroU32::new(value).unwrap()
+}
+
+/// [`RateLimiter`] and [`Quota`] implement common traits.
+#[wasm_bindgen_test(unsupported = test)]
+const fn traits() {
+	assert_impl_all!(RateLimiter: Clone, Send, Sync);
+	assert_impl_all!(Quota: Clone, Copy, Eq, Send, Sync);
+}
+
+/// [`Quota::token_bucket()`] refills multiple tokens at once.
+#[wasm_bindgen_test(unsupported = test)]
+fn token_bucket() {
+	let clock = ManualClock::new();
+	let quota = Quota::token_bucket(non_zero(3), non_zero(2), Duration::from_millis(100));
+	let mut limiter = RateLimiter::with_clock(&clock, quota);
+	assert_eq!(limiter.quota(), quota);
+
+	for _ in 0..3 {
+		assert_eq!(limiter.until_ready(), Duration::ZERO);
+		assert!(limiter.check());
+	}
+
+	assert!(!limiter.check());
+	assert_eq!(limiter.until_ready(), Duration::from_millis(100));
+
+	clock.advance(Duration::from_millis(50));
+	assert!(!limiter.check());
+	assert_eq!(limiter.until_ready(), Duration::from_millis(50));
+
+	clock.advance(Duration::from_millis(50));
+	assert!(limiter.check());
+	assert!(limiter.check());
+	assert!(!limiter.check());
+
+	// Refills keep their schedule.
+	clock.advance(Duration::from_millis(150));
+	assert!(limiter.check());
+	assert!(limiter.check());
+	assert!(!limiter.check());
+	assert_eq!(limiter.until_ready(), Duration::from_millis(50));
+
+	// Doesn't exceed the capacity.
+	clock.advance(Duration::from_secs(1));
+
+	for _ in 0..3 {
+		assert!(limiter.check());
+	}
+
+	assert!(!limiter.check());
+	assert_eq!(limiter.until_ready(), Duration::from_millis(100));
+}
+
+/// [`Quota::gcra()`] spaces cells evenly.
+#[wasm_bindgen_test(unsupported = test)]
+fn gcra() {
+	let clock = ManualClock::new();
+	let quota = Quota::gcra(non_zero(10), Duration::from_secs(1), non_zero(2));
+	let mut limiter = RateLimiter::with_clock(&clock, quota);
+
+	assert!(limiter.check());
+	assert!(limiter.check());
+	assert!(!limiter.check());
+	assert_eq!(limiter.until_ready(), Duration::from_millis(100));
+
+	clock.advance(Duration::from_millis(50));
+	assert!(!limiter.check());
+	assert_eq!(limiter.until_ready(), Duration::from_millis(50));
+
+	clock.advance(Duration::from_millis(50));
+	assert!(limiter.check());
+	assert!(!limiter.check());
+
+	// Restores the burst over time.
+	clock.advance(Duration::from_secs(1));
+	assert!(limiter.check());
+	assert!(limiter.check());
+	assert!(!limiter.check());
+
+	// Without a burst.
+	let quota = Quota::gcra(non_zero(10), Duration::from_secs(1), non_zero(1));
+	let mut limiter = RateLimiter::with_clock(&clock, quota);
+
+	for _ in 0..10 {
+		assert!(limiter.check());
+		assert!(!limiter.check());
+		assert_eq!(limiter.until_ready(), Duration::from_millis(100));
+		clock.advance(Duration::from_millis(100));
+	}
+}
+
+/// A zero period doesn't limit the rate.
+#[wasm_bindgen_test(unsupported = test)]
+fn unlimited() {
+	let clock = ManualClock::new();
+
+	for quota in [
+		Quota::token_bucket(non_zero(1), non_zero(1), Duration::ZERO),
+		Quota::gcra(non_zero(1), Duration::ZERO, non_zero(1)),
+	] {
+		let mut limiter = RateLimiter::with_clock(&clock, quota);
+
+		for _ in 0..100 {
+			assert!(limiter.check());
+			assert_eq!(limiter.until_ready(), Duration::ZERO);
+		}
+	}
+}
+
+/// Periods exceeding [`Instant`](web_time::Instant) never become ready again.
+#[wasm_bindgen_test(unsupported = test)]
+fn never() {
+	let clock = ManualClock::new();
+
+	for quota in [
+		Quota::token_bucket(non_zero(1), non_zero(1), Duration::MAX),
+		Quota::gcra(non_zero(1), Duration::MAX, non_zero(1)),
+	] {
+		let mut limiter = RateLimiter::with_clock(&clock, quota);
+		assert!(limiter.check());
+		assert!(!limiter.check());
+		assert_eq!(limiter.until_ready(), Duration::MAX);
+
+		clock.advance(Duration::from_secs(1));
+		assert!(!limiter.check());
+	}
+}
+
+/// [`RateLimiter::reset()`] restores the full burst.
+#[wasm_bindgen_test(unsupported = test)]
+fn reset() {
+	let clock = ManualClock::new();
+
+	for quota in [
+		Quota::token_bucket(non_zero(2), non_zero(1), Duration::from_secs(1)),
+		Quota::gcra(non_zero(1), Duration::from_secs(1), non_zero(2)),
+	] {
+		let mut limiter = RateLimiter::with_clock(&clock, quota);
+		assert!(limiter.check());
+		assert!(limiter.check());
+		assert!(!limiter.check());
+
+		limiter.reset();
+		assert!(limiter.check());
+		assert!(limiter.check());
+		assert!(!limiter.check());
+	}
+}
+
+/// [`RateLimiter::acquire()`] waits until ready.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+async fn acquire() {
+	use web_time::Instant;
+
+	use self::util::{DIFF, MAX_DIFF, WAIT};
+
+	let mut limiter = RateLimiter::new(Quota::gcra(non_zero(1), WAIT, non_zero(1)));
+
+	let start = Instant::now();
+	limiter.acquire().await;
+	assert!(start.elapsed() < DIFF);
+
+	limiter.acquire().await;
+	let elapsed = start.elapsed();
+	assert!(elapsed >= DIFF, "{elapsed:?}");
+	assert!(elapsed <= MAX_DIFF);
+}
+
+/// [`RateLimiter::acquire()`] doesn't panic but stays pending if the next cell
+/// can't be allowed before [`Instant`](web_time::Instant) overflows.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+async fn acquire_never() {
+	use core::future::{poll_fn, Future};
+	use core::pin::pin;
+	use core::task::Poll;
+
+	for quota in [
+		Quota::token_bucket(non_zero(1), non_zero(1), Duration::MAX),
+		Quota::gcra(non_zero(1), Duration::MAX, non_zero(1)),
+	] {
+		let mut limiter = RateLimiter::new(quota);
+		limiter.acquire().await;
+
+		let mut acquire = pin!(limiter.acquire());
+		assert!(poll_fn(|cx| Poll::Ready(acquire.as_mut().poll(cx)))
+			.await
+			.is_pending());
+	}
+}
@@ -0,0 +1,38 @@
+//! [`register_clock!`](web_time::register_clock) tests.
+
+#![cfg(test)]
+
+use web_time::Duration;
+
+/// Registered monotonic clock.
+const fn instant_now() -> Duration {
+	Duration::from_secs(42)
+}
+
+web_time::register_clock!(instant_now);
+
+extern "Rust" {
+	/// Symbol defined by [`register_clock!`](web_time::register_clock).
+	fn __web_time_instant_now() -> Duration;
+
+	/// Symbol defined by [`register_clock!`](web_time::register_clock).
+	fn __web_time_system_time_now() -> Duration;
+}
+
+/// The registered function is called for [`Instant`](web_time::Instant).
+#[test]
+fn instant() {
+	// SAFETY: Defined by `register_clock!` with this signature.
+	#[expect(unsafe_code, reason = "test")]
+	let now = unsafe { __web_time_instant_now() };
+	assert_eq!(now, Duration::from_secs(42));
+}
+
+/// Without a registered function [`SystemTime`](web_time::SystemTime) panics.
+#[test]
+#[should_panic = "no system time clock registered"]
+fn system_time() {
+	// SAFETY: Defined by `register_clock!` with this signature.
+	#[expect(unsafe_code, reason = "test")]
+	let _now = unsafe { __web_time_system_time_now() };
+}
@@ -0,0 +1,115 @@
+//! [`Stopwatch`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+extern crate alloc;
+
+mod util;
+
+use alloc::format;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Duration, Stopwatch};
+
+use self::util::{sleep, ManualClock, DIFF, MAX_DIFF, WAIT};
+
+/// [`Stopwatch::elapsed()`] with [`Instant::now()`].
+#[wasm_bindgen_test(unsupported = pollster::test)]
+async fn elapsed() {
+	let stopwatch = Stopwatch::start_new();
+	sleep(WAIT).await;
+	let elapsed = stopwatch.elapsed();
+	assert!(elapsed >= DIFF, "{elapsed:?}");
+	assert!(elapsed <= MAX_DIFF);
+}
+
+/// [`Stopwatch::new()`] doesn't start.
+#[wasm_bindgen_test(unsupported = pollster::test)]
+async fn idle() {
+	let stopwatch = Stopwatch::new();
+	sleep(WAIT).await;
+	assert!(!stopwatch.is_running());
+	assert_eq!(stopwatch.elapsed(), Duration::ZERO);
+}
+
+/// Start, stop and restart.
+#[wasm_bindgen_test(unsupported = test)]
+fn start_stop() {
+	let clock = ManualClock::new();
+	let mut stopwatch = Stopwatch::with_clock(&clock);
+	clock.advance(Duration::from_secs(1));
+	assert_eq!(stopwatch.elapsed(), Duration::ZERO);
+
+	stopwatch.start();
+	assert!(stopwatch.is_running());
+	clock.advance(Duration::from_secs(2));
+	assert_eq!(stopwatch.elapsed(), Duration::from_secs(2));
+	assert_eq!(stopwatch.stop(), Duration::from_secs(2));
+	assert!(stopwatch.is_stopped());
+
+	clock.advance(Duration::from_secs(3));
+	stopwatch.resume();
+	assert!(stopwatch.is_stopped());
+	assert_eq!(stopwatch.elapsed(), Duration::from_secs(2));
+
+	stopwatch.start();
+	clock.advance(Duration::from_secs(4));
+	assert_eq!(stopwatch.elapsed(), Duration::from_secs(4));
+}
+
+/// Paused time is excluded.
+#[wasm_bindgen_test(unsupported = test)]
+fn pause_resume() {
+	let clock = ManualClock::new();
+	let mut stopwatch = Stopwatch::start_with_clock(&clock);
+	clock.advance(Duration::from_secs(1));
+	stopwatch.pause();
+	assert!(stopwatch.is_paused());
+	clock.advance(Duration::from_secs(10));
+	assert_eq!(stopwatch.elapsed(), Duration::from_secs(1));
+
+	stopwatch.resume();
+	assert!(stopwatch.is_running());
+	clock.advance(Duration::from_secs(2));
+	assert_eq!(stopwatch.elapsed(), Duration::from_secs(3));
+}
+
+/// Laps exclude paused time.
+#[wasm_bindgen_test(unsupported = test)]
+fn laps() {
+	let clock = ManualClock::new();
+	let mut stopwatch = Stopwatch::start_with_clock(&clock);
+	clock.advance(Duration::from_secs(1));
+	assert_eq!(stopwatch.current_lap(), Duration::from_secs(1));
+	assert_eq!(stopwatch.lap(), Duration::from_secs(1));
+	assert_eq!(stopwatch.current_lap(), Duration::ZERO);
+
+	clock.advance(Duration::from_secs(2));
+	stopwatch.pause();
+	clock.advance(Duration::from_secs(10));
+	stopwatch.resume();
+	clock.advance(Duration::from_secs(3));
+	assert_eq!(stopwatch.lap(), Duration::from_secs(5));
+	assert_eq!(stopwatch.laps(), 2);
+	assert_eq!(stopwatch.elapsed(), Duration::from_secs(6));
+
+	stopwatch.reset();
+	assert_eq!(stopwatch.laps(), 0);
+	assert_eq!(stopwatch.elapsed(), Duration::ZERO);
+}
+
+/// [`Display`](core::fmt::Display) output.
+#[wasm_bindgen_test(unsupported = test)]
+fn display() {
+	let clock = ManualClock::new();
+	let mut stopwatch = Stopwatch::start_with_clock(&clock);
+	clock.advance(Duration::from_secs(1));
+	assert_eq!(format!("{stopwatch}"), "1s");
+
+	clock.advance(Duration::from_micros(1500));
+	stopwatch.stop();
+	assert_eq!(format!("{stopwatch}"), "1.0015s");
+	assert_eq!(format!("{stopwatch:.2}"), "1.00s");
+}
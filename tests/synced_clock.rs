@@ -0,0 +1,247 @@
+//! [`SyncedClock`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::{SampleError, SyncedClock};
+use web_time::{Duration, SystemTime};
+
+use self::util::ManualClock;
+
+/// Time of the fake server when [`ManualClock`] was created.
+const SERVER_START: Duration = Duration::from_secs(1_700_000_000);
+
+/// Fake server returning its time.
+fn server(clock: &ManualClock) -> SystemTime {
+	SystemTime::UNIX_EPOCH + SERVER_START + (clock.now() - clock.start())
+}
+
+/// Adds a sample to `synced` with the given delays to and from the fake
+/// server. Its time is shifted by `skew`.
+fn round_trip(
+	synced: &mut SyncedClock<&ManualClock>,
+	clock: &ManualClock,
+	to: Duration,
+	from: Duration,
+	skew: Duration,
+) {
+	let send = clock.now();
+	clock.advance(to);
+	let server_time = server(clock) + skew;
+	clock.advance(from);
+	synced.add_sample(send, server_time, clock.now()).unwrap();
+}
+
+/// Returns the distance between two [`SystemTime`]s.
+fn distance(left: SystemTime, right: SystemTime) -> Duration {
+	left.duration_since(right)
+		.unwrap_or_else(|error| error.duration())
+}
+
+/// No samples.
+#[wasm_bindgen_test(unsupported = test)]
+fn empty() {
+	let clock = ManualClock::new();
+	let synced = SyncedClock::with_clock(&clock);
+	assert_eq!(synced.now(), None);
+	assert_eq!(synced.uncertainty(), None);
+	assert_eq!(synced.samples(), 0);
+}
+
+/// Symmetric delay is estimated exactly.
+#[wasm_bindgen_test(unsupported = test)]
+fn symmetric() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	let delay = Duration::from_millis(100);
+	round_trip(&mut synced, &clock, delay, delay, Duration::ZERO);
+
+	assert_eq!(synced.now(), Some(server(&clock)));
+	assert_eq!(synced.uncertainty(), Some(delay));
+	assert_eq!(synced.samples(), 1);
+
+	clock.advance(Duration::from_secs(30));
+	assert_eq!(synced.now(), Some(server(&clock)));
+}
+
+/// Asymmetric delay stays within the uncertainty.
+#[wasm_bindgen_test(unsupported = test)]
+fn asymmetric() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	round_trip(
+		&mut synced,
+		&clock,
+		Duration::from_millis(10),
+		Duration::from_millis(190),
+		Duration::ZERO,
+	);
+
+	let uncertainty = synced.uncertainty().unwrap();
+	assert_eq!(uncertainty, Duration::from_millis(100));
+	assert!(distance(synced.now().unwrap(), server(&clock)) <= uncertainty);
+}
+
+/// Multiple samples narrow down the uncertainty.
+#[wasm_bindgen_test(unsupported = test)]
+fn narrowing() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	round_trip(
+		&mut synced,
+		&clock,
+		Duration::from_millis(10),
+		Duration::from_millis(190),
+		Duration::ZERO,
+	);
+	round_trip(
+		&mut synced,
+		&clock,
+		Duration::from_millis(190),
+		Duration::from_millis(10),
+		Duration::ZERO,
+	);
+
+	let uncertainty = synced.uncertainty().unwrap();
+	assert!(uncertainty < Duration::from_millis(100), "{uncertainty:?}");
+	assert!(distance(synced.now().unwrap(), server(&clock)) <= uncertainty);
+	assert_eq!(synced.samples(), 2);
+}
+
+/// Outliers are rejected.
+#[wasm_bindgen_test(unsupported = test)]
+fn outlier() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	let delay = Duration::from_millis(50);
+
+	for _ in 0..3 {
+		round_trip(&mut synced, &clock, delay, delay, Duration::ZERO);
+	}
+
+	round_trip(&mut synced, &clock, delay, delay, Duration::from_secs(1000));
+
+	assert_eq!(synced.samples(), 3);
+	assert_eq!(synced.now(), Some(server(&clock)));
+}
+
+/// Only the most recent samples are retained.
+#[wasm_bindgen_test(unsupported = test)]
+fn capacity() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	let delay = Duration::from_millis(50);
+	let skew = Duration::from_secs(1000);
+
+	for _ in 0..8 {
+		round_trip(&mut synced, &clock, delay, delay, Duration::ZERO);
+	}
+
+	for _ in 0..5 {
+		round_trip(&mut synced, &clock, delay, delay, skew);
+	}
+
+	assert_eq!(synced.samples(), 5);
+	assert_eq!(synced.now(), Some(server(&clock) + skew));
+
+	synced.reset();
+	assert_eq!(synced.now(), None);
+}
+
+/// Corrected time at an earlier [`Instant`](web_time::Instant).
+#[wasm_bindgen_test(unsupported = test)]
+fn system_time_at() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	clock.advance(Duration::from_secs(10));
+	let delay = Duration::from_millis(100);
+	round_trip(&mut synced, &clock, delay, delay, Duration::ZERO);
+
+	assert_eq!(
+		synced.system_time_at(clock.start()),
+		Some(SystemTime::UNIX_EPOCH + SERVER_START)
+	);
+}
+
+/// Receiving before sending fails.
+#[wasm_bindgen_test(unsupported = test)]
+fn negative_round_trip() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	let send = clock.now();
+	clock.advance(Duration::from_secs(1));
+
+	assert_eq!(
+		synced.add_sample(clock.now(), server(&clock), send),
+		Err(SampleError::NegativeRoundTrip)
+	);
+	assert_eq!(synced.now(), None);
+}
+
+/// HTTP `Date` header samples.
+#[wasm_bindgen_test(unsupported = test)]
+fn date_header() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	let send = clock.now();
+	clock.advance(Duration::from_millis(200));
+	synced
+		.add_date_header(send, "Sun, 06 Nov 1994 08:49:37 GMT", clock.now())
+		.unwrap();
+
+	// 784111777 is 1994-11-06T08:49:37Z.
+	let date = SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777);
+	let now = synced.now().unwrap();
+	assert_eq!(synced.uncertainty(), Some(Duration::from_millis(600)));
+	assert!(now >= date);
+	assert!(now <= date + Duration::from_millis(1100));
+}
+
+/// Invalid HTTP `Date` headers.
+#[wasm_bindgen_test(unsupported = test)]
+fn date_header_invalid() {
+	let clock = ManualClock::new();
+	let mut synced = SyncedClock::with_clock(&clock);
+	let now = clock.now();
+
+	for date in [
+		"",
+		"Sun, 06 Nov 1994 08:49:37 UTC",
+		"Mon, 06 Nov 1994 08:49:37 GMT",
+		"Sun, 31 Nov 1994 08:49:37 GMT",
+		"Sun, 06 Nov 1994 24:49:37 GMT",
+		"Thu, 01 Jan 1970 00:00:0a GMT",
+	] {
+		assert_eq!(
+			synced.add_date_header(now, date, now),
+			Err(SampleError::InvalidDate),
+			"{date}"
+		);
+	}
+
+	synced
+		.add_date_header(now, "Thu, 01 Jan 1970 00:00:00 GMT", now)
+		.unwrap();
+	synced
+		.add_date_header(now, "Thu, 29 Feb 2024 23:59:59 GMT", now)
+		.unwrap();
+}
+
+/// Synchronizing with the local [`SystemTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn local() {
+	let mut synced = SyncedClock::new();
+	let send = web_time::Instant::now();
+	let time = SystemTime::now();
+	synced
+		.add_sample(send, time, web_time::Instant::now())
+		.unwrap();
+
+	let now = SystemTime::now();
+	let corrected = synced.now().unwrap();
+	assert!(distance(corrected, now) <= synced.uncertainty().unwrap() + Duration::from_millis(10));
+}
@@ -0,0 +1,143 @@
+//! Conversions and arithmetic between [`SystemTime`], [`Instant`] and [`time`]
+//! types.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+#![cfg_attr(
+	not(target_arch = "wasm32"),
+	expect(
+		clippy::unnecessary_fallible_conversions,
+		reason = "conversions to `SystemTime` are only fallible on Web"
+	)
+)]
+
+mod util;
+
+use time::macros::datetime;
+use time::{Duration as TimeDuration, OffsetDateTime, UtcDateTime};
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Duration, Instant, SystemTime};
+
+/// 1994-11-06T08:49:37Z.
+const DATE: Duration = Duration::from_secs(784_111_777);
+
+/// [`SystemTime`] to [`OffsetDateTime`] and [`UtcDateTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn to_date_time() {
+	let time = SystemTime::UNIX_EPOCH;
+	assert_eq!(OffsetDateTime::from(time), OffsetDateTime::UNIX_EPOCH);
+	assert_eq!(UtcDateTime::from(time), UtcDateTime::UNIX_EPOCH);
+
+	let time = SystemTime::UNIX_EPOCH + DATE + Duration::from_nanos(123_456_789);
+	assert_eq!(
+		OffsetDateTime::from(time),
+		datetime!(1994-11-06 08:49:37.123_456_789 UTC)
+	);
+	assert_eq!(
+		UtcDateTime::from(time),
+		datetime!(1994-11-06 08:49:37.123_456_789 UTC).to_utc()
+	);
+}
+
+/// [`OffsetDateTime`] and [`UtcDateTime`] to [`SystemTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn from_date_time() {
+	let expected = SystemTime::UNIX_EPOCH + DATE;
+	assert_eq!(
+		SystemTime::try_from(datetime!(1994-11-06 08:49:37 UTC)).unwrap(),
+		expected
+	);
+	assert_eq!(
+		SystemTime::try_from(datetime!(1994-11-06 09:49:37 +1)).unwrap(),
+		expected
+	);
+	assert_eq!(
+		SystemTime::try_from(datetime!(1994-11-06 08:49:37 UTC).to_utc()).unwrap(),
+		expected
+	);
+}
+
+/// Sub-second precision round-trips.
+#[wasm_bindgen_test(unsupported = test)]
+fn round_trip() {
+	for nanos in [0, 1, 999_999_999] {
+		let time = SystemTime::UNIX_EPOCH + DATE + Duration::from_nanos(nanos);
+		assert_eq!(SystemTime::try_from(OffsetDateTime::from(time)), Ok(time));
+		assert_eq!(SystemTime::try_from(UtcDateTime::from(time)), Ok(time));
+	}
+}
+
+/// Times before the Unix epoch.
+#[wasm_bindgen_test(unsupported = test)]
+fn before_epoch() {
+	let date_time = datetime!(1969-12-31 23:59:58.5 UTC);
+
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		let time = SystemTime::UNIX_EPOCH - Duration::from_millis(1500);
+		assert_eq!(OffsetDateTime::from(time), date_time);
+		assert_eq!(SystemTime::try_from(date_time), Ok(time));
+		assert_eq!(SystemTime::try_from(date_time.to_utc()), Ok(time));
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	{
+		use time::error::ConversionRange;
+
+		assert_eq!(SystemTime::try_from(date_time), Err(ConversionRange));
+		assert_eq!(
+			SystemTime::try_from(date_time.to_utc()),
+			Err(ConversionRange)
+		);
+	}
+}
+
+/// Arithmetic of [`SystemTime`] with [`TimeDuration`].
+#[wasm_bindgen_test(unsupported = test)]
+fn system_time_arithmetic() {
+	let time = SystemTime::UNIX_EPOCH + DATE;
+	let later = time + Duration::from_millis(1500);
+
+	assert_eq!(time + TimeDuration::milliseconds(1500), later);
+	assert_eq!(later + TimeDuration::milliseconds(-1500), time);
+	assert_eq!(later - TimeDuration::milliseconds(1500), time);
+	assert_eq!(time - TimeDuration::milliseconds(-1500), later);
+	assert_eq!(time + TimeDuration::ZERO, time);
+
+	let mut value = time;
+	value += TimeDuration::milliseconds(1500);
+	assert_eq!(value, later);
+	value -= TimeDuration::milliseconds(1500);
+	assert_eq!(value, time);
+}
+
+/// Arithmetic of [`Instant`] with [`TimeDuration`].
+#[wasm_bindgen_test(unsupported = test)]
+fn instant_arithmetic() {
+	let instant = Instant::now();
+	let later = instant + Duration::from_millis(1500);
+
+	assert_eq!(instant + TimeDuration::milliseconds(1500), later);
+	assert_eq!(later + TimeDuration::milliseconds(-1500), instant);
+	assert_eq!(later - TimeDuration::milliseconds(1500), instant);
+	assert_eq!(instant - TimeDuration::milliseconds(-1500), later);
+
+	let mut value = instant;
+	value += TimeDuration::milliseconds(1500);
+	assert_eq!(value, later);
+	value -= TimeDuration::milliseconds(1500);
+	assert_eq!(value, instant);
+}
+
+/// [`Duration`] conversions with [`TimeDuration`].
+#[wasm_bindgen_test(unsupported = test)]
+fn duration() {
+	let duration = Duration::new(5, 123_456_789);
+	let time_duration = TimeDuration::try_from(duration).unwrap();
+	assert_eq!(time_duration, TimeDuration::new(5, 123_456_789));
+	assert_eq!(Duration::try_from(time_duration), Ok(duration));
+	assert_eq!(time_duration.unsigned_abs(), duration);
+	TimeDuration::try_from(Duration::MAX).unwrap_err();
+	Duration::try_from(-time_duration).unwrap_err();
+}
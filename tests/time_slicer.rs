@@ -0,0 +1,149 @@
+//! Tests for [`TimeSlicer`] and [`web::yield_now()`].
+
+#![cfg(test)]
+#![no_main]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod util;
+
+use core::cell::Cell;
+use core::future::{poll_fn, Future};
+use core::num::NonZeroU32;
+use core::pin::Pin;
+use core::task::Poll;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::{self, TimeSlicer};
+use web_time::{Duration, Instant};
+
+use self::util::ManualClock;
+
+/// Budget used in tests.
+const BUDGET: Duration = Duration::from_millis(8);
+
+/// [`TimeSlicer::should_yield()`] after the budget is used up.
+#[wasm_bindgen_test]
+fn should_yield() {
+	let clock = ManualClock::new();
+	let mut slicer = TimeSlicer::with_clock(&clock, BUDGET);
+	assert_eq!(slicer.budget(), BUDGET);
+	assert_eq!(slicer.remaining(), BUDGET);
+	assert!(!slicer.should_yield());
+
+	clock.advance(Duration::from_millis(7));
+	assert!(!slicer.should_yield());
+	assert_eq!(slicer.remaining(), Duration::from_millis(1));
+
+	clock.advance(Duration::from_millis(1));
+	assert!(slicer.should_yield());
+	assert_eq!(slicer.remaining(), Duration::ZERO);
+
+	slicer.reset();
+	assert!(!slicer.should_yield());
+	assert_eq!(slicer.remaining(), BUDGET);
+}
+
+/// [`TimeSlicer::should_yield()`] keeps returning `true` until reset.
+#[wasm_bindgen_test]
+fn sticky() {
+	let start = Instant::now();
+	let offset = Cell::new(BUDGET);
+	let mut slicer = TimeSlicer::with_clock(|| start + offset.get(), BUDGET);
+
+	offset.set(BUDGET * 2);
+	assert!(slicer.should_yield());
+
+	// Going back in time doesn't un-expire the slice.
+	offset.set(BUDGET);
+	assert!(slicer.should_yield());
+}
+
+/// [`TimeSlicer::with_sample_interval()`] only checks the time every n-th
+/// call.
+#[wasm_bindgen_test]
+fn sample_interval() {
+	let clock = ManualClock::new();
+	let samples = Cell::new(0);
+	let mut slicer = TimeSlicer::with_clock(
+		|| {
+			samples.set(samples.get() + 1);
+			clock.now()
+		},
+		BUDGET,
+	)
+	.with_sample_interval(NonZeroU32::new(10).unwrap());
+	assert_eq!(samples.get(), 1);
+
+	for _ in 0..9 {
+		assert!(!slicer.should_yield());
+	}
+
+	assert_eq!(samples.get(), 1);
+	assert!(!slicer.should_yield());
+	assert_eq!(samples.get(), 2);
+
+	clock.advance(BUDGET);
+
+	for _ in 0..9 {
+		assert!(!slicer.should_yield());
+	}
+
+	assert_eq!(samples.get(), 2);
+	assert!(slicer.should_yield());
+	assert_eq!(samples.get(), 3);
+	assert!(slicer.should_yield());
+	assert_eq!(samples.get(), 3);
+
+	slicer.reset();
+	assert_eq!(samples.get(), 4);
+
+	for _ in 0..10 {
+		assert!(!slicer.should_yield());
+	}
+
+	assert_eq!(samples.get(), 5);
+}
+
+/// A budget that can't be represented never ends.
+#[wasm_bindgen_test]
+fn unbounded() {
+	let mut slicer = TimeSlicer::new(Duration::MAX);
+	assert!(!slicer.should_yield());
+	assert_eq!(slicer.remaining(), Duration::MAX);
+}
+
+/// [`web::yield_now()`] returns to the event loop.
+#[wasm_bindgen_test]
+async fn yield_now() {
+	let mut future = web::yield_now();
+	assert!(poll_fn(|cx| Poll::Ready(Pin::new(&mut future).poll(cx)))
+		.await
+		.is_pending());
+	future.await;
+
+	// Dropping before completion.
+	let mut future = web::yield_now();
+	assert!(poll_fn(|cx| Poll::Ready(Pin::new(&mut future).poll(cx)))
+		.await
+		.is_pending());
+	drop(future);
+	web::yield_now().await;
+}
+
+/// [`TimeSlicer::yield_if_needed()`] yields once the budget is used up.
+#[wasm_bindgen_test]
+async fn yield_if_needed() {
+	let mut slicer = TimeSlicer::new(Duration::from_millis(1));
+	assert!(!slicer.yield_if_needed().await);
+
+	let start = Instant::now();
+	let mut yields = 0;
+
+	while start.elapsed() < Duration::from_millis(20) {
+		if slicer.yield_if_needed().await {
+			yields += 1;
+		}
+	}
+
+	assert!(yields >= 2, "{yields}");
+}
@@ -0,0 +1,205 @@
+//! Tests for [`Delay`] and [`Timer`].
+
+#![cfg(test)]
+#![no_main]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod util;
+
+use core::future::{poll_fn, Future};
+use core::pin::Pin;
+use core::task::Poll;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::{Delay, Timer};
+use web_time::{Duration, Instant};
+
+use self::util::{DIFF, MAX_DIFF, WAIT};
+
+/// [`Delay`] completes after its deadline.
+#[wasm_bindgen_test]
+async fn delay() {
+	let start = Instant::now();
+	let delay = Delay::new(WAIT);
+	assert!(delay.deadline() >= start + WAIT);
+
+	delay.await;
+	let duration = start.elapsed();
+	assert!(duration >= DIFF, "{duration:?}");
+	assert!(duration <= MAX_DIFF);
+}
+
+/// [`Delay::reset()`] to an earlier deadline wakes the task without polling
+/// again.
+#[wasm_bindgen_test]
+async fn delay_reset() {
+	let start = Instant::now();
+	let mut delay = Delay::new(Duration::from_secs(1_000));
+	let mut reset = false;
+
+	poll_fn(|cx| {
+		let poll = Pin::new(&mut delay).poll(cx);
+
+		if !reset {
+			reset = true;
+			delay.reset(start + WAIT);
+		}
+
+		poll
+	})
+	.await;
+
+	let duration = start.elapsed();
+	assert!(duration >= DIFF, "{duration:?}");
+	assert!(duration <= MAX_DIFF);
+}
+
+/// [`Delay::reset()`] after completion.
+#[wasm_bindgen_test]
+async fn delay_reset_completed() {
+	let mut delay = Delay::at(Instant::now());
+	(&mut delay).await;
+
+	let start = Instant::now();
+	delay.reset(start + WAIT);
+	delay.await;
+	assert!(start.elapsed() >= DIFF);
+}
+
+/// Many [`Delay`]s complete in order of their deadlines.
+#[wasm_bindgen_test]
+async fn delay_many() {
+	let start = Instant::now();
+	let mut delays = [7, 3, 5, 1, 9, 2, 8, 4, 6].map(|step| {
+		let deadline = start + Duration::from_millis(step * 10);
+		(Delay::at(deadline), None)
+	});
+
+	poll_fn(|cx| {
+		let mut pending = false;
+
+		for (delay, completed) in &mut delays {
+			if completed.is_none() {
+				if Pin::new(&mut *delay).poll(cx).is_ready() {
+					*completed = Some(Instant::now());
+				} else {
+					pending = true;
+				}
+			}
+		}
+
+		if pending {
+			Poll::Pending
+		} else {
+			Poll::Ready(())
+		}
+	})
+	.await;
+
+	for (delay, completed) in delays {
+		let completed = completed.unwrap();
+		assert!(completed >= delay.deadline());
+		assert!(completed - delay.deadline() <= MAX_DIFF);
+	}
+}
+
+/// [`Timer::after()`] and [`Timer::at()`].
+#[wasm_bindgen_test]
+async fn timer() {
+	let start = Instant::now();
+	let timer = Timer::after(WAIT);
+	assert!(timer.will_fire());
+
+	let fired = timer.await;
+	assert!(fired >= start + WAIT);
+	assert!(Instant::now() >= fired);
+	assert!(start.elapsed() <= MAX_DIFF);
+
+	let instant = Instant::now() - Duration::from_millis(1);
+	assert_eq!(Timer::at(instant).await, instant);
+}
+
+/// [`Timer::never()`] doesn't fire.
+#[wasm_bindgen_test]
+async fn never() {
+	let mut timer = Timer::never();
+	assert!(!timer.will_fire());
+	assert!(poll_fn(|cx| Poll::Ready(Pin::new(&mut timer).poll(cx)))
+		.await
+		.is_pending());
+
+	let mut timer = Timer::after(Duration::MAX);
+	assert!(!timer.will_fire());
+	timer.set_after(Duration::ZERO);
+	assert!(timer.will_fire());
+	timer.await;
+}
+
+/// [`Timer::interval()`] fires every period without drifting.
+#[wasm_bindgen_test]
+async fn interval() {
+	let period = Duration::from_millis(10);
+	let start = Instant::now();
+	let mut timer = Timer::interval_at(start + period, period);
+
+	for step in 1..=3 {
+		let fired = (&mut timer).await;
+		assert_eq!(fired, start + period * step);
+		assert!(Instant::now() >= fired);
+		assert!(timer.will_fire());
+	}
+
+	timer.set_interval(WAIT);
+	let start = Instant::now();
+	(&mut timer).await;
+	assert!(start.elapsed() >= DIFF);
+}
+
+/// [`Timer::set_at()`] moves a pending registration.
+#[wasm_bindgen_test]
+async fn set_at() {
+	let start = Instant::now();
+	let mut timer = Timer::interval(Duration::from_secs(1_000));
+	let mut set = false;
+
+	let fired = poll_fn(|cx| {
+		let poll = Pin::new(&mut timer).poll(cx);
+
+		if !set {
+			set = true;
+			timer.set_at(start + WAIT);
+		}
+
+		poll
+	})
+	.await;
+
+	assert_eq!(fired, start + WAIT);
+	assert!(start.elapsed() >= DIFF);
+	assert!(!timer.will_fire());
+}
+
+/// `Stream` implementation of [`Timer`].
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn stream() {
+	use futures_core::{FusedStream, Stream};
+
+	let instant = Instant::now();
+	let mut timer = Timer::at(instant);
+	assert!(!timer.is_terminated());
+
+	let next = poll_fn(|cx| Pin::new(&mut timer).poll_next(cx)).await;
+	assert_eq!(next, Some(instant));
+	assert!(timer.is_terminated());
+	let next = poll_fn(|cx| Pin::new(&mut timer).poll_next(cx)).await;
+	assert_eq!(next, None);
+
+	let period = Duration::from_millis(10);
+	let mut timer = Timer::interval_at(instant, period);
+
+	for step in 0..3 {
+		let next = poll_fn(|cx| Pin::new(&mut timer).poll_next(cx)).await;
+		assert_eq!(next, Some(instant + period * step));
+	}
+}
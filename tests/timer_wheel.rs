@@ -0,0 +1,314 @@
+//! Tests for [`TimerWheel`].
+
+#![cfg(test)]
+#![no_main]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod util;
+
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use alloc::vec::Vec;
+use core::future::poll_fn;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use static_assertions::assert_impl_all;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::{TimerKey, TimerWheel};
+use web_time::{Duration, Instant};
+
+use self::util::{ManualClock, DIFF, MAX_DIFF, WAIT};
+
+/// Counts how often it was woken.
+struct Counter(AtomicUsize);
+
+impl Counter {
+	/// Creates a new [`Counter`] and its [`Waker`].
+	fn new() -> (Arc<Self>, Waker) {
+		let counter = Arc::new(Self(AtomicUsize::new(0)));
+		let waker = Waker::from(Arc::clone(&counter));
+		(counter, waker)
+	}
+
+	/// Returns how often it was woken.
+	fn get(&self) -> usize {
+		self.0.load(Ordering::Relaxed)
+	}
+}
+
+impl Wake for Counter {
+	fn wake(self: Arc<Self>) {
+		self.0.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+/// [`TimerWheel`] can be sent to other threads.
+#[wasm_bindgen_test]
+const fn traits() {
+	assert_impl_all!(TimerWheel: Send, Sync);
+	assert_impl_all!(TimerKey: Clone, Copy, Send, Sync);
+}
+
+/// Timers expire exactly at their deadline across all levels.
+#[wasm_bindgen_test]
+fn expire() {
+	let clock = ManualClock::new();
+	let mut wheel = TimerWheel::with_clock(&clock);
+	assert!(wheel.is_empty());
+	assert_eq!(wheel.next_expiration(), None);
+
+	let mut timers: Vec<_> = [
+		1,
+		5,
+		63,
+		64,
+		65,
+		100,
+		4_095,
+		4_096,
+		4_097,
+		10_000,
+		262_200,
+		1_000_000,
+		100_000_000,
+		// Beyond the range of all levels.
+		100_000_000_000,
+	]
+	.into_iter()
+	.map(|millis| {
+		let deadline = clock.start() + Duration::from_millis(millis);
+		let (counter, waker) = Counter::new();
+		let key = wheel.insert(deadline, &waker);
+		assert_eq!(wheel.deadline(key), Some(deadline));
+		(deadline, key, counter)
+	})
+	.collect();
+	assert_eq!(wheel.len(), timers.len());
+
+	// Insert in reverse order as well.
+	timers.extend(
+		[7, 4_100, 300_000]
+			.into_iter()
+			.rev()
+			.map(|millis| {
+				let deadline = clock.start() + Duration::from_millis(millis);
+				let (counter, waker) = Counter::new();
+				(deadline, wheel.insert(deadline, &waker), counter)
+			})
+			.collect::<Vec<_>>(),
+	);
+	timers.sort_by_key(|(deadline, ..)| *deadline);
+
+	for (index, (deadline, key, counter)) in timers.iter().enumerate() {
+		let next = wheel.next_expiration().unwrap();
+		assert!(next <= *deadline);
+
+		clock.advance(*deadline - Duration::from_millis(1) - clock.now());
+		wheel.process();
+		assert_eq!(counter.get(), 0);
+		assert_eq!(wheel.deadline(*key), Some(*deadline));
+
+		clock.advance(Duration::from_millis(1));
+		assert_eq!(wheel.process(), 1);
+		assert_eq!(counter.get(), 1);
+		assert_eq!(wheel.deadline(*key), None);
+		assert_eq!(wheel.len(), timers.len() - index - 1);
+	}
+
+	assert!(wheel.is_empty());
+	assert_eq!(wheel.next_expiration(), None);
+}
+
+/// Timers are never woken before their deadline.
+#[wasm_bindgen_test]
+fn precision() {
+	let clock = ManualClock::new();
+	let mut wheel = TimerWheel::with_clock(&clock);
+	let (counter, waker) = Counter::new();
+
+	wheel.insert(clock.start() + Duration::from_micros(1_500), &waker);
+	clock.advance(Duration::from_micros(1_500));
+	assert_eq!(wheel.process(), 0);
+	clock.advance(Duration::from_micros(500));
+	assert_eq!(wheel.process(), 1);
+	assert_eq!(counter.get(), 1);
+}
+
+/// Multiple timers with the same deadline and timers that already expired.
+#[wasm_bindgen_test]
+fn batch() {
+	let clock = ManualClock::new();
+	let mut wheel = TimerWheel::with_clock(&clock);
+	let (counter, waker) = Counter::new();
+
+	for _ in 0..100 {
+		wheel.insert(clock.start() + Duration::from_millis(10), &waker);
+	}
+
+	clock.advance(Duration::from_millis(20));
+	assert_eq!(wheel.process(), 100);
+	assert_eq!(counter.get(), 100);
+
+	wheel.insert(clock.start(), &waker);
+	wheel.insert(clock.now(), &waker);
+	assert_eq!(wheel.next_expiration(), Some(clock.now()));
+	assert_eq!(wheel.process(), 2);
+	assert_eq!(counter.get(), 102);
+}
+
+/// [`TimerWheel::cancel()`] removes timers without waking them.
+#[wasm_bindgen_test]
+fn cancel() {
+	let clock = ManualClock::new();
+	let mut wheel = TimerWheel::with_clock(&clock);
+	let (counter, waker) = Counter::new();
+
+	let keys: Vec<_> = (1..=10)
+		.map(|millis| wheel.insert(clock.start() + Duration::from_millis(millis), &waker))
+		.collect();
+
+	for key in keys.iter().step_by(2) {
+		assert!(wheel.cancel(*key));
+		assert!(!wheel.cancel(*key));
+		assert_eq!(wheel.deadline(*key), None);
+	}
+
+	assert_eq!(wheel.len(), 5);
+
+	// Reused storage doesn't resurrect cancelled keys.
+	let new = wheel.insert(clock.start() + Duration::from_millis(20), &waker);
+	assert!(!keys.contains(&new));
+	assert!(keys
+		.iter()
+		.step_by(2)
+		.all(|key| wheel.deadline(*key).is_none()));
+
+	clock.advance(Duration::from_millis(20));
+	assert_eq!(wheel.process(), 6);
+	assert_eq!(counter.get(), 6);
+	assert!(!wheel.cancel(new));
+}
+
+/// [`TimerWheel::poll_timer()`] replaces the registered [`Waker`].
+#[wasm_bindgen_test]
+fn poll_timer() {
+	let clock = ManualClock::new();
+	let mut wheel = TimerWheel::with_clock(&clock);
+	let (old, old_waker) = Counter::new();
+	let (new, new_waker) = Counter::new();
+	let cx = Context::from_waker(&new_waker);
+
+	let key = wheel.insert(clock.start() + Duration::from_millis(10), &old_waker);
+	assert!(wheel.poll_timer(key, &cx).is_pending());
+
+	clock.advance(Duration::from_millis(10));
+	assert_eq!(wheel.process(), 1);
+	assert_eq!(old.get(), 0);
+	assert_eq!(new.get(), 1);
+	assert!(wheel.poll_timer(key, &cx).is_ready());
+
+	// Expired but not processed yet.
+	let key = wheel.insert(clock.now() + Duration::from_millis(10), &old_waker);
+	clock.advance(Duration::from_millis(10));
+	assert!(wheel.poll_timer(key, &cx).is_ready());
+	assert!(wheel.is_empty());
+	assert_eq!(wheel.process(), 0);
+}
+
+/// [`TimerWheel::poll_process()`] wakes timers with `setTimeout()`.
+#[wasm_bindgen_test]
+async fn poll_process() {
+	let mut wheel = TimerWheel::new();
+	let start = Instant::now();
+	let (counter, waker) = Counter::new();
+
+	for step in 1..=3 {
+		wheel.insert(start + WAIT * step, &waker);
+	}
+
+	for step in 1..=3 {
+		let fired = poll_fn(|cx| wheel.poll_process(cx)).await;
+		assert_eq!(fired, 1);
+		assert_eq!(counter.get(), usize::try_from(step).unwrap());
+
+		let elapsed = start.elapsed();
+		assert!(elapsed >= WAIT * step, "{elapsed:?}");
+		assert!(elapsed <= WAIT * step + MAX_DIFF);
+	}
+
+	assert!(wheel.is_empty());
+}
+
+/// Inserting an earlier timer wakes the task driving the [`TimerWheel`].
+#[wasm_bindgen_test]
+async fn poll_process_insert() {
+	let mut wheel = TimerWheel::new();
+	let start = Instant::now();
+	let (counter, waker) = Counter::new();
+	wheel.insert(start + Duration::from_secs(1_000), &waker);
+	let mut inserted = false;
+
+	poll_fn(|cx| {
+		let poll = wheel.poll_process(cx);
+
+		if !inserted {
+			inserted = true;
+			wheel.insert(start + WAIT, &waker);
+		}
+
+		poll
+	})
+	.await;
+
+	let elapsed = start.elapsed();
+	assert!(elapsed >= DIFF, "{elapsed:?}");
+	assert!(elapsed <= MAX_DIFF);
+	assert_eq!(counter.get(), 1);
+	assert_eq!(wheel.len(), 1);
+
+	// Empty wheels are woken as well.
+	let mut wheel = TimerWheel::new();
+	let start = Instant::now();
+	let mut inserted = false;
+
+	poll_fn(|cx| {
+		let poll = wheel.poll_process(cx);
+
+		if !inserted {
+			inserted = true;
+			assert!(poll.is_pending());
+			wheel.insert(start + WAIT, &waker);
+		}
+
+		poll
+	})
+	.await;
+
+	assert!(start.elapsed() >= DIFF);
+	assert!(wheel.is_empty());
+}
+
+/// Timers with a [`TimerKey`] are driven by [`TimerWheel::poll_timer()`].
+#[wasm_bindgen_test]
+async fn future() {
+	let mut wheel = TimerWheel::new();
+	let start = Instant::now();
+	let mut key = None;
+
+	poll_fn(|cx| {
+		let key = *key.get_or_insert_with(|| wheel.insert(start + WAIT, cx.waker()));
+
+		if wheel.poll_timer(key, cx).is_ready() {
+			return Poll::Ready(());
+		}
+
+		let _ = wheel.poll_process(cx);
+		Poll::Pending
+	})
+	.await;
+
+	assert!(start.elapsed() >= DIFF);
+}
@@ -0,0 +1,222 @@
+//! [`web_time::tokio_compat`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+mod util;
+
+use core::future::{self, Future};
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll};
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::tokio_compat::{self as time, Duration, Instant, MissedTickBehavior};
+
+use self::util::{DIFF, MAX_DIFF, WAIT};
+
+/// [`time::sleep()`].
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn sleep() {
+	let start = Instant::now();
+	let sleep = time::sleep(WAIT);
+	assert!(sleep.deadline() >= start + WAIT);
+	assert!(!sleep.is_elapsed());
+
+	sleep.await;
+	let duration = start.elapsed();
+	assert!(duration >= DIFF, "{duration:?}");
+	assert!(duration <= MAX_DIFF);
+}
+
+/// [`time::sleep_until()`] with a deadline in the past completes immediately.
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn sleep_until_past() {
+	let start = Instant::now();
+	time::sleep_until(start - Duration::from_millis(1)).await;
+	assert!(start.elapsed() < DIFF);
+}
+
+/// [`time::Sleep::reset()`].
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn sleep_reset() {
+	let start = Instant::now();
+	let mut sleep = pin!(time::sleep(Duration::from_secs(1_000)));
+	sleep.as_mut().reset(start + WAIT);
+	assert_eq!(sleep.deadline(), start + WAIT);
+
+	sleep.as_mut().await;
+	let duration = start.elapsed();
+	assert!(duration >= DIFF, "{duration:?}");
+	assert!(duration <= MAX_DIFF);
+	assert!(sleep.is_elapsed());
+}
+
+/// [`time::timeout()`] with a future completing in time.
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn timeout_ready() {
+	let timeout = time::timeout(WAIT, future::ready(42));
+	assert_eq!(timeout.get_ref().clone().await, 42);
+	assert_eq!(timeout.await, Ok(42));
+}
+
+/// [`time::timeout()`] with a future not completing in time.
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn timeout_elapsed() {
+	let start = Instant::now();
+	time::timeout(WAIT, future::pending::<()>())
+		.await
+		.unwrap_err();
+	let duration = start.elapsed();
+	assert!(duration >= DIFF, "{duration:?}");
+	assert!(duration <= MAX_DIFF);
+}
+
+/// [`time::timeout_at()`] with a deadline in the past still polls the future.
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn timeout_at_past() {
+	let deadline = Instant::now() - Duration::from_millis(1);
+	assert_eq!(time::timeout_at(deadline, future::ready(())).await, Ok(()));
+	time::timeout_at(deadline, future::pending::<()>())
+		.await
+		.unwrap_err();
+}
+
+/// [`time::Timeout::into_inner()`].
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn timeout_into_inner() {
+	let mut timeout = time::timeout(WAIT, future::ready(1));
+	*timeout.get_mut() = future::ready(2);
+	assert_eq!(timeout.into_inner().await, 2);
+}
+
+/// [`time::error::Elapsed`] formatting and conversion.
+#[cfg(feature = "std")]
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn elapsed() {
+	use std::io::{Error, ErrorKind};
+
+	let error = time::timeout(Duration::ZERO, future::pending::<()>())
+		.await
+		.unwrap_err();
+	assert_eq!(error.to_string(), "deadline has elapsed");
+	assert_eq!(Error::from(error).kind(), ErrorKind::TimedOut);
+}
+
+/// [`time::interval()`] ticks immediately and then after every period.
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn interval() {
+	let start = Instant::now();
+	let mut interval = time::interval(WAIT);
+	assert_eq!(interval.period(), WAIT);
+	assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Burst);
+
+	let first = interval.tick().await;
+	assert!(first >= start);
+	assert!(start.elapsed() < DIFF);
+
+	let second = interval.tick().await;
+	assert_eq!(second, first + WAIT);
+	let duration = start.elapsed();
+	assert!(duration >= DIFF, "{duration:?}");
+	assert!(duration <= MAX_DIFF);
+}
+
+/// [`time::interval_at()`] with [`MissedTickBehavior`].
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn interval_missed_tick() {
+	let period = Duration::from_millis(10);
+
+	for behavior in [
+		MissedTickBehavior::Burst,
+		MissedTickBehavior::Delay,
+		MissedTickBehavior::Skip,
+	] {
+		let before = Instant::now();
+		let start = before - Duration::from_millis(25);
+		let mut interval = time::interval_at(start, period);
+		interval.set_missed_tick_behavior(behavior);
+
+		assert_eq!(interval.tick().await, start);
+		let second = interval.tick().await;
+
+		match behavior {
+			MissedTickBehavior::Burst => assert_eq!(second, start + period),
+			MissedTickBehavior::Delay => assert!(second >= before + period),
+			MissedTickBehavior::Skip => {
+				assert!(second > before);
+				assert_eq!(
+					(second - start).as_nanos() % period.as_nanos(),
+					0,
+					"{second:?}"
+				);
+			}
+		}
+	}
+}
+
+/// [`time::Interval::reset()`] and friends.
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn interval_reset() {
+	let mut interval = time::interval(Duration::from_secs(1_000));
+	interval.tick().await;
+
+	let start = Instant::now();
+	interval.reset_immediately();
+	interval.tick().await;
+	assert!(start.elapsed() < DIFF);
+
+	interval.reset_after(WAIT);
+	let tick = interval.tick().await;
+	assert!(tick >= start + WAIT);
+
+	let deadline = Instant::now() + WAIT;
+	interval.reset_at(deadline);
+	assert_eq!(interval.tick().await, deadline);
+}
+
+/// [`time::Interval::poll_tick()`] registers the waker.
+#[wasm_bindgen_test(unsupported = tokio::test)]
+async fn interval_poll_tick() {
+	struct PollTick<'interval>(&'interval mut time::Interval);
+
+	impl Future for PollTick<'_> {
+		type Output = Instant;
+
+		fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+			self.0.poll_tick(cx)
+		}
+	}
+
+	let start = Instant::now();
+	let mut interval = time::interval_at(start + WAIT, WAIT);
+	assert_eq!(PollTick(&mut interval).await, start + WAIT);
+	assert!(start.elapsed() >= DIFF);
+}
+
+/// [`Instant`] conversions and arithmetic.
+#[wasm_bindgen_test(unsupported = test)]
+fn instant() {
+	let std = web_time::Instant::now();
+	let instant = Instant::from_std(std);
+	assert_eq!(instant.into_std(), std);
+	assert_eq!(Instant::from(std), instant);
+	assert_eq!(web_time::Instant::from(instant), std);
+
+	let later = instant + WAIT;
+	assert_eq!(later - instant, WAIT);
+	assert_eq!(instant - later, Duration::ZERO);
+	assert_eq!(instant.duration_since(later), Duration::ZERO);
+	assert_eq!(instant.checked_duration_since(later), None);
+	assert_eq!(later.checked_duration_since(instant), Some(WAIT));
+	assert_eq!(instant.saturating_duration_since(later), Duration::ZERO);
+	assert_eq!(instant.checked_add(WAIT), Some(later));
+	assert_eq!(later.checked_sub(WAIT), Some(instant));
+	assert_eq!(later - WAIT, instant);
+
+	let mut value = instant;
+	value += WAIT;
+	assert_eq!(value, later);
+	value -= WAIT;
+	assert_eq!(value, instant);
+}
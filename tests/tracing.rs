@@ -0,0 +1,110 @@
+//! Test [`tracing-subscriber`] integration exported in
+//! [`web_time::web::tracing`].
+//!
+//! [`tracing-subscriber`]: https://docs.rs/tracing-subscriber/0.3
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+
+mod util;
+
+use core::ops::Range;
+
+use tracing::subscriber;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::layer::SubscriberExt;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::web::tracing::{PerformanceLayer, Rfc3339, Uptime};
+use web_time::{Duration, SystemTime, UtcDateTime};
+
+use self::util::{sleep, DIFF, MAX_DIFF, WAIT};
+
+/// Returns the number of [`PerformanceEntry`]s with the given name.
+///
+/// [`PerformanceEntry`]: https://developer.mozilla.org/en-US/docs/Web/API/PerformanceEntry
+#[cfg(target_arch = "wasm32")]
+fn entries(name: &str) -> u32 {
+	use js_sys::Array;
+	use wasm_bindgen::prelude::wasm_bindgen;
+
+	#[wasm_bindgen]
+	extern "C" {
+		#[wasm_bindgen(js_namespace = performance, js_name = getEntriesByName)]
+		fn get_entries_by_name(name: &str) -> Array;
+	}
+
+	get_entries_by_name(name).length()
+}
+
+/// [`Rfc3339`] writes the current time.
+#[wasm_bindgen_test(unsupported = test)]
+fn rfc3339() {
+	let before = SystemTime::now();
+	let mut output = String::new();
+	Rfc3339.format_time(&mut Writer::new(&mut output)).unwrap();
+	let after = SystemTime::now();
+
+	// E.g. `2024-01-01T00:00:00.000000Z`.
+	assert_eq!(output.len(), 27, "{output}");
+	let separators: String = output
+		.chars()
+		.filter(|char| !char.is_ascii_digit())
+		.collect();
+	assert_eq!(separators, "--T::.Z", "{output}");
+
+	let number = |range: Range<usize>| output.get(range).unwrap().parse::<u32>().unwrap();
+	let field = |range| u8::try_from(number(range)).unwrap();
+	let written = UtcDateTime::new(
+		number(0..4).into(),
+		field(5..7),
+		field(8..10),
+		field(11..13),
+		field(14..16),
+		field(17..19),
+		number(20..26) * 1000,
+	)
+	.unwrap()
+	.to_system_time()
+	.unwrap();
+
+	// Microseconds are truncated.
+	assert!(written + Duration::from_micros(1) > before, "{output}");
+	assert!(written <= after, "{output}");
+}
+
+/// [`Uptime`].
+#[wasm_bindgen_test(unsupported = pollster::test)]
+async fn uptime() {
+	let uptime = Uptime::default();
+	sleep(WAIT).await;
+
+	let mut output = String::new();
+	uptime.format_time(&mut Writer::new(&mut output)).unwrap();
+
+	let (secs, nanos) = output
+		.trim_start()
+		.strip_suffix('s')
+		.unwrap()
+		.split_once('.')
+		.unwrap();
+	assert_eq!(secs, "0", "{output}");
+	assert_eq!(nanos.len(), 9, "{output}");
+	let elapsed = Duration::from_nanos(nanos.parse().unwrap());
+	assert!(elapsed >= DIFF, "{output}");
+	assert!(elapsed <= MAX_DIFF, "{output}");
+}
+
+/// [`PerformanceLayer`].
+#[wasm_bindgen_test(unsupported = test)]
+fn layer() {
+	let registry = tracing_subscriber::registry().with(PerformanceLayer);
+
+	subscriber::with_default(registry, || {
+		let span = tracing::info_span!("web-time-span");
+		span.in_scope(|| tracing::info!("web-time-event"));
+	});
+
+	#[cfg(target_arch = "wasm32")]
+	assert_eq!(entries("web-time-span"), 1);
+}
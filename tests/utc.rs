@@ -0,0 +1,328 @@
+//! [`UtcDateTime`] tests.
+
+#![cfg(test)]
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg_attr(all(target_arch = "wasm32", not(feature = "std")), no_std)]
+
+extern crate alloc;
+
+mod util;
+
+use alloc::string::ToString;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::{Duration, SystemTime, UtcDateTime, Weekday};
+
+/// Days in a 400 year cycle of the Gregorian calendar.
+const DAYS_PER_ERA: u64 = 146_097;
+
+/// Naive calendar used to verify [`UtcDateTime`].
+#[derive(Clone, Copy, Debug)]
+struct Naive {
+	/// Year.
+	year: i64,
+	/// Month.
+	month: u8,
+	/// Day.
+	day: u8,
+	/// Day of the year.
+	ordinal: u16,
+	/// Day of the week, starting with Monday at `0`.
+	weekday: u8,
+}
+
+impl Naive {
+	/// 1970-01-01.
+	const EPOCH: Self = Self {
+		year: 1970,
+		month: 1,
+		day: 1,
+		ordinal: 1,
+		weekday: 3,
+	};
+
+	/// Returns [`true`] if the year is a leap year.
+	const fn is_leap_year(year: i64) -> bool {
+		year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+	}
+
+	/// Returns the number of days in the month.
+	const fn days_in_month(year: i64, month: u8) -> u8 {
+		match month {
+			1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+			4 | 6 | 9 | 11 => 30,
+			_ if Self::is_leap_year(year) => 29,
+			_ => 28,
+		}
+	}
+
+	/// Advances to the next day.
+	fn next(&mut self) {
+		self.weekday = (self.weekday + 1) % 7;
+
+		if self.day < Self::days_in_month(self.year, self.month) {
+			self.day += 1;
+			self.ordinal += 1;
+		} else if self.month < 12 {
+			self.month += 1;
+			self.day = 1;
+			self.ordinal += 1;
+		} else {
+			self.year += 1;
+			self.month = 1;
+			self.day = 1;
+			self.ordinal = 1;
+		}
+	}
+
+	/// Goes back to the previous day.
+	#[cfg_attr(target_arch = "wasm32", expect(dead_code, reason = "only native"))]
+	fn previous(&mut self) {
+		self.weekday = (self.weekday + 6) % 7;
+
+		if self.day > 1 {
+			self.day -= 1;
+			self.ordinal -= 1;
+		} else if self.month > 1 {
+			self.month -= 1;
+			self.day = Self::days_in_month(self.year, self.month);
+			self.ordinal -= 1;
+		} else {
+			self.year -= 1;
+			self.month = 12;
+			self.day = 31;
+			self.ordinal = if Self::is_leap_year(self.year) {
+				366
+			} else {
+				365
+			};
+		}
+	}
+
+	/// Asserts that `date_time` matches this date.
+	fn assert(self, date_time: UtcDateTime) {
+		assert_eq!(date_time.year(), self.year, "{self:?}");
+		assert_eq!(date_time.month(), self.month, "{self:?}");
+		assert_eq!(date_time.day(), self.day, "{self:?}");
+		assert_eq!(date_time.ordinal(), self.ordinal, "{self:?}");
+		assert_eq!(
+			date_time.weekday().number_days_from_monday(),
+			self.weekday,
+			"{self:?}"
+		);
+	}
+}
+
+/// Asserts that `time` round-trips through [`UtcDateTime`] and returns it.
+fn round_trip(time: SystemTime) -> UtcDateTime {
+	let date_time = UtcDateTime::from(time);
+	assert_eq!(date_time.to_system_time(), Some(time), "{date_time:?}");
+	let new = UtcDateTime::new(
+		date_time.year(),
+		date_time.month(),
+		date_time.day(),
+		date_time.hour(),
+		date_time.minute(),
+		date_time.second(),
+		date_time.nanosecond(),
+	);
+	assert_eq!(new, Some(date_time));
+	date_time
+}
+
+/// Every day of two full 400 year cycles after the Unix epoch.
+#[wasm_bindgen_test(unsupported = test)]
+fn exhaustive_after_epoch() {
+	let mut naive = Naive::EPOCH;
+
+	for day in 0..DAYS_PER_ERA * 2 {
+		// Vary the time of day to cover all hours, minutes and seconds.
+		let secs = day * 86400 + (day * 7919) % 86400;
+		let time = SystemTime::UNIX_EPOCH + Duration::new(secs, 0);
+		let date_time = round_trip(time);
+		naive.assert(date_time);
+
+		let secs_of_day = secs % 86400;
+		assert_eq!(u64::from(date_time.hour()), secs_of_day / 3600);
+		assert_eq!(u64::from(date_time.minute()), secs_of_day % 3600 / 60);
+		assert_eq!(u64::from(date_time.second()), secs_of_day % 60);
+
+		naive.next();
+	}
+}
+
+/// Every day of two full 400 year cycles before the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn exhaustive_before_epoch() {
+	let mut naive = Naive::EPOCH;
+
+	for day in 1..=DAYS_PER_ERA * 2 {
+		naive.previous();
+
+		let time = SystemTime::UNIX_EPOCH - Duration::from_secs(day * 86400);
+		let date_time = round_trip(time);
+		naive.assert(date_time);
+		assert_eq!(date_time.hour(), 0);
+
+		let date_time = round_trip(time + Duration::new(86399, 999_999_999));
+		naive.assert(date_time);
+		assert_eq!(date_time.hour(), 23);
+		assert_eq!(date_time.minute(), 59);
+		assert_eq!(date_time.second(), 59);
+		assert_eq!(date_time.nanosecond(), 999_999_999);
+	}
+}
+
+/// Sub-second precision before and after the Unix epoch.
+#[wasm_bindgen_test(unsupported = test)]
+fn nanosecond() {
+	let date_time = round_trip(SystemTime::UNIX_EPOCH + Duration::from_nanos(1));
+	assert_eq!(date_time.to_string(), "1970-01-01T00:00:00.000000001Z");
+
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		let date_time = round_trip(SystemTime::UNIX_EPOCH - Duration::from_millis(500));
+		assert_eq!(date_time.to_string(), "1969-12-31T23:59:59.5Z");
+	}
+}
+
+/// Applies the largest amount of seconds possible to
+/// [`SystemTime::UNIX_EPOCH`] with `apply`.
+fn limit(apply: fn(&SystemTime, Duration) -> Option<SystemTime>) -> SystemTime {
+	let mut low = 0;
+	let mut high = u64::MAX;
+
+	while low < high {
+		let middle = low + (high - low) / 2 + 1;
+
+		if apply(&SystemTime::UNIX_EPOCH, Duration::from_secs(middle)).is_some() {
+			low = middle;
+		} else {
+			high = middle - 1;
+		}
+	}
+
+	apply(&SystemTime::UNIX_EPOCH, Duration::from_secs(low)).unwrap()
+}
+
+/// Largest [`SystemTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn max() {
+	let max = limit(SystemTime::checked_add);
+	let max = max
+		.checked_add(Duration::from_nanos(999_999_999))
+		.unwrap_or(max);
+	let date_time = round_trip(max);
+	assert_eq!(
+		date_time
+			.to_system_time()
+			.unwrap()
+			.checked_add(Duration::from_nanos(1)),
+		None
+	);
+
+	#[cfg(target_arch = "wasm32")]
+	assert_eq!(
+		date_time.to_string(),
+		"584554051223-11-09T07:00:15.999999999Z"
+	);
+}
+
+/// Smallest [`SystemTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn min() {
+	let date_time = round_trip(limit(SystemTime::checked_sub));
+
+	#[cfg(target_arch = "wasm32")]
+	assert_eq!(date_time.to_string(), "1970-01-01T00:00:00Z");
+	#[cfg(not(target_arch = "wasm32"))]
+	assert!(date_time.year() < 0);
+}
+
+/// Dates outside the range of [`SystemTime`].
+#[wasm_bindgen_test(unsupported = test)]
+fn out_of_range() {
+	let date_time = UtcDateTime::new(i64::MAX, 12, 31, 23, 59, 59, 999_999_999).unwrap();
+	assert_eq!(date_time.to_system_time(), None);
+	assert_eq!(date_time.year(), i64::MAX);
+	let date_time = UtcDateTime::new(i64::MIN, 1, 1, 0, 0, 0, 0).unwrap();
+	assert_eq!(date_time.to_system_time(), None);
+	assert_eq!(
+		date_time.to_string(),
+		"-9223372036854775808-01-01T00:00:00Z"
+	);
+}
+
+/// Invalid components.
+#[wasm_bindgen_test(unsupported = test)]
+fn invalid() {
+	assert!(UtcDateTime::new(2024, 2, 29, 0, 0, 0, 0).is_some());
+	assert!(UtcDateTime::new(2000, 2, 29, 0, 0, 0, 0).is_some());
+	assert!(UtcDateTime::new(0, 2, 29, 0, 0, 0, 0).is_some());
+	assert!(UtcDateTime::new(-4, 2, 29, 0, 0, 0, 0).is_some());
+
+	for (year, month, day, hour, minute, second, nanosecond) in [
+		(2023, 2, 29, 0, 0, 0, 0),
+		(1900, 2, 29, 0, 0, 0, 0),
+		(-1, 2, 29, 0, 0, 0, 0),
+		(2024, 0, 1, 0, 0, 0, 0),
+		(2024, 13, 1, 0, 0, 0, 0),
+		(2024, 1, 0, 0, 0, 0, 0),
+		(2024, 4, 31, 0, 0, 0, 0),
+		(2024, 1, 1, 24, 0, 0, 0),
+		(2024, 1, 1, 0, 60, 0, 0),
+		(2024, 1, 1, 0, 0, 60, 0),
+		(2024, 1, 1, 0, 0, 0, 1_000_000_000),
+	] {
+		assert_eq!(
+			UtcDateTime::new(year, month, day, hour, minute, second, nanosecond),
+			None,
+			"{year}-{month}-{day}T{hour}:{minute}:{second}.{nanosecond}"
+		);
+	}
+}
+
+/// [`Display`](core::fmt::Display) output.
+#[wasm_bindgen_test(unsupported = test)]
+fn display() {
+	for (date_time, expected) in [
+		((1994, 11, 6, 8, 49, 37, 0), "1994-11-06T08:49:37Z"),
+		(
+			(1994, 11, 6, 8, 49, 37, 500_000_000),
+			"1994-11-06T08:49:37.5Z",
+		),
+		(
+			(1994, 11, 6, 8, 49, 37, 120_000),
+			"1994-11-06T08:49:37.00012Z",
+		),
+		((1, 1, 1, 0, 0, 0, 0), "0001-01-01T00:00:00Z"),
+		((0, 1, 1, 0, 0, 0, 0), "0000-01-01T00:00:00Z"),
+		((-1, 12, 31, 23, 59, 59, 0), "-0001-12-31T23:59:59Z"),
+		((12345, 1, 1, 0, 0, 0, 0), "12345-01-01T00:00:00Z"),
+	] {
+		let (year, month, day, hour, minute, second, nanosecond) = date_time;
+		let date_time =
+			UtcDateTime::new(year, month, day, hour, minute, second, nanosecond).unwrap();
+		assert_eq!(date_time.to_string(), expected);
+	}
+}
+
+/// [`Weekday`] numbering.
+#[wasm_bindgen_test(unsupported = test)]
+fn weekday() {
+	let date_time = UtcDateTime::new(1, 1, 1, 0, 0, 0, 0).unwrap();
+	assert_eq!(date_time.weekday(), Weekday::Monday);
+	assert_eq!(Weekday::Monday.number_from_monday(), 1);
+	assert_eq!(Weekday::Monday.number_days_from_monday(), 0);
+	assert_eq!(Weekday::Sunday.number_from_monday(), 7);
+	assert_eq!(Weekday::Sunday.number_days_from_monday(), 6);
+}
+
+/// Ordering is chronological.
+#[wasm_bindgen_test(unsupported = test)]
+fn ord() {
+	let earlier = UtcDateTime::new(-1, 12, 31, 23, 59, 59, 999_999_999).unwrap();
+	let later = UtcDateTime::new(0, 1, 1, 0, 0, 0, 0).unwrap();
+	assert!(earlier < later);
+}
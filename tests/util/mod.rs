@@ -5,9 +5,11 @@ mod std;
 #[cfg(target_arch = "wasm32")]
 mod web;
 
+use core::cell::Cell;
+
 #[cfg(target_arch = "wasm32")]
 use tests_web as _;
-use web_time::Duration;
+use web_time::{Clock, Duration, Instant};
 
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(
@@ -36,3 +38,44 @@ pub(crate) const MAX_DIFF: Duration = if let Some(duration) = WAIT.checked_mul(1
 } else {
 	panic!()
 };
+
+/// Manually controlled [`Clock`].
+#[allow(clippy::allow_attributes, dead_code, reason = "not used by all tests")]
+pub(crate) struct ManualClock {
+	/// Fixed starting point.
+	start: Instant,
+	/// Time passed since `start`.
+	offset: Cell<Duration>,
+}
+
+#[allow(clippy::allow_attributes, dead_code, reason = "not used by all tests")]
+impl ManualClock {
+	/// Creates a new [`ManualClock`].
+	pub(crate) fn new() -> Self {
+		Self {
+			start: Instant::now(),
+			offset: Cell::new(Duration::ZERO),
+		}
+	}
+
+	/// Returns the current [`Instant`] of this clock.
+	pub(crate) fn now(&self) -> Instant {
+		self.start + self.offset.get()
+	}
+
+	/// Returns the starting point of this clock.
+	pub(crate) const fn start(&self) -> Instant {
+		self.start
+	}
+
+	/// Advances the clock by the given [`Duration`].
+	pub(crate) fn advance(&self, duration: Duration) {
+		self.offset.set(self.offset.get() + duration);
+	}
+}
+
+impl Clock for &ManualClock {
+	fn now(&self) -> Instant {
+		ManualClock::now(self)
+	}
+}